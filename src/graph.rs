@@ -1,23 +1,185 @@
 use std::{
-    collections::{HashMap, VecDeque},
-    rc::Rc,
+    marker::PhantomData,
+    ops::{Add, Index, IndexMut, Sub},
 };
 
-type RefNode = Rc<Node>;
+mod bidirectional;
+mod bridges;
+mod centrality;
+mod coloring;
+mod communities;
+mod components;
+mod csr;
+mod cycles;
+mod dot;
+mod error;
+mod euler;
+mod flow;
+mod graphml;
+mod metrics;
+mod mst;
+mod scc;
+#[cfg(feature = "serde")]
+mod serialize;
+mod shortest_path;
+mod spatial;
+mod text;
+mod traversal;
+mod union_find;
+mod yen;
 
-pub struct Edge {
-    pub weight: u32,
-    pub from: RefNode,
-    pub to: RefNode,
+pub use self::coloring::{Coloring, ColoringOrder};
+pub use self::communities::Communities;
+pub use self::components::Components;
+pub use self::csr::Csr;
+pub use self::cycles::Cycle;
+pub use self::dot::DotAttributes;
+pub use self::error::ParseError;
+pub use self::euler::Trail;
+pub use self::flow::{EdgeFlow, Flow};
+pub use self::graphml::{AttrType, AttrValue, Attributes, GraphMl, GraphMlData};
+pub use self::metrics::Eccentricities;
+pub use self::mst::SpanningTree;
+pub use self::shortest_path::{Heuristic, Path, Position, ShortestPaths};
+pub use self::spatial::GridPosition;
+pub use self::traversal::{Bfs, Control, Dfs, Visitor};
+pub use self::union_find::UnionFind;
+
+use self::spatial::SpatialIndex;
+
+// edge weights the weighted searches can add up and compare
+// they should never be negative
+pub trait Weight: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    fn zero() -> Self;
+    // rounds down so heuristics built from it never overestimate
+    fn from_distance(distance: f64) -> Self;
+    // for averages and ratios of weights
+    fn as_f64(self) -> f64;
 }
 
-impl Edge {
-    pub fn from(from: RefNode, to: RefNode, weight: u32) -> Edge {
+macro_rules! impl_weight {
+    ($($t:ty),*) => {
+        $(impl Weight for $t {
+            fn zero() -> Self {
+                0
+            }
+            fn from_distance(distance: f64) -> Self {
+                distance as $t
+            }
+            fn as_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+impl_weight!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+// index of a node payload in the graph's node arena
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeId(usize);
+
+impl NodeId {
+    pub fn new(index: usize) -> NodeId {
+        NodeId(index)
+    }
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+// index of an edge in the graph's edge arena
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EdgeId(usize);
+
+impl EdgeId {
+    pub fn new(index: usize) -> EdgeId {
+        EdgeId(index)
+    }
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Edge<E> {
+    pub weight: E,
+    pub from: NodeId,
+    pub to: NodeId,
+}
+
+impl<E> Edge<E> {
+    pub fn from(from: NodeId, to: NodeId, weight: E) -> Edge<E> {
         Edge { from, to, weight }
     }
 }
 
-#[derive(Eq, Hash)]
+// an edge as seen from one of its endpoints, `from` is always the node it was reached from
+#[derive(Debug)]
+pub struct EdgeRef<'a, E> {
+    pub id: EdgeId,
+    pub from: NodeId,
+    pub to: NodeId,
+    pub weight: &'a E,
+}
+
+impl<'a, E> Clone for EdgeRef<'a, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<'a, E> Copy for EdgeRef<'a, E> {}
+
+// marker types picking how a graph stores and reports its edges
+pub trait EdgeType {
+    fn is_directed() -> bool;
+}
+
+// edges only go from `from` to `to`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Directed;
+
+// edges can be walked both ways, each one is stored once
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Undirected;
+
+impl EdgeType for Directed {
+    fn is_directed() -> bool {
+        true
+    }
+}
+
+impl EdgeType for Undirected {
+    fn is_directed() -> bool {
+        false
+    }
+}
+
+pub type DiGraph<N, E> = Graph<N, E, Directed>;
+pub type UnGraph<N, E> = Graph<N, E, Undirected>;
+
+// read only view of a graph's edges, the traversals and shortest path searches are written against it
+// so they run the same on a Graph and on its frozen Csr form
+pub trait Adjacency {
+    type Weight;
+
+    fn node_count(&self) -> usize;
+    // edges leaving a node, oriented so `from` is the given node
+    fn edges_from(&self, id: NodeId) -> impl Iterator<Item = EdgeRef<'_, Self::Weight>>;
+    // edges that can be walked into a node, oriented so `to` is the given node
+    fn edges_to(&self, id: NodeId) -> impl Iterator<Item = EdgeRef<'_, Self::Weight>>;
+
+    fn contains_node(&self, id: NodeId) -> bool {
+        id.0 < self.node_count()
+    }
+}
+
+// node payload used by the maze graphs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Node {
     pub x: u32,
     pub y: u32,
@@ -25,211 +187,414 @@ pub struct Node {
 }
 impl Node {
     pub fn new() -> Node {
-        Node {
-            x: 0,
-            y: 0,
-            vec_coord: 0,
-        }
+        Node::default()
     }
     pub fn from(x: u32, y: u32, vec_coord: usize) -> Node {
         Node { x, y, vec_coord }
     }
 }
 
-impl PartialEq for Node {
-    fn eq(&self, other: &Self) -> bool {
-        self.vec_coord == other.vec_coord
+// N is the node payload and E the edge weight, nodes and edges are referred to by index
+// Ty says whether the edges are one way (Directed) or two way (Undirected)
+#[derive(Clone, Debug)]
+pub struct Graph<N, E, Ty = Undirected> {
+    nodes: Vec<N>,
+    edges: Vec<Edge<E>>,
+    // edges leaving every node, indexed the same as `nodes`
+    // undirected edges are listed under both of their endpoints
+    outgoing: Vec<Vec<EdgeId>>,
+    // edges entering every node, only filled in for directed graphs
+    incoming: Vec<Vec<EdgeId>>,
+    // optional position lookup, see build_spatial_index
+    spatial: Option<SpatialIndex<N>>,
+    ty: PhantomData<Ty>,
+}
+
+impl<N, E, Ty> Default for Graph<N, E, Ty> {
+    fn default() -> Self {
+        Graph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            outgoing: Vec::new(),
+            incoming: Vec::new(),
+            spatial: None,
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<N, E> Graph<N, E, Undirected> {
+    pub fn new() -> Self {
+        Graph::default()
     }
 }
 
-pub struct Graph {
-    pub nodes: Vec<RefNode>,
-    pub edges: HashMap<RefNode, Vec<Edge>>,
+impl<N, E> Graph<N, E, Directed> {
+    pub fn new_directed() -> Self {
+        Graph::default()
+    }
 }
 
-impl Graph {
-    pub fn new() -> Graph {
+impl<N, E, Ty: EdgeType> Graph<N, E, Ty> {
+    pub fn with_capacity(nodes: usize, edges: usize) -> Self {
         Graph {
-            nodes: Vec::new(),
-            edges: HashMap::new(),
+            nodes: Vec::with_capacity(nodes),
+            edges: Vec::with_capacity(edges),
+            outgoing: Vec::with_capacity(nodes),
+            incoming: Vec::with_capacity(if Ty::is_directed() { nodes } else { 0 }),
+            spatial: None,
+            ty: PhantomData,
         }
     }
 
-    pub fn add_node(&mut self, x: u32, y: u32, vec_coord: usize) -> RefNode {
-        let new_node = Rc::new(Node::from(x, y, vec_coord));
-        let return_node = Rc::clone(&new_node);
-        self.nodes.push(new_node);
-        return_node
+    pub fn is_directed(&self) -> bool {
+        Ty::is_directed()
     }
 
-    pub fn find_node_xy(&self, x: u32, y: u32) -> Option<RefNode> {
-        for node in &self.nodes {
-            if node.x == x && node.y == y {
-                return Some(Rc::clone(node));
-            }
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn add_node(&mut self, payload: N) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        if let Some(index) = &mut self.spatial {
+            index.settle(&self.nodes);
+            index.insert(&payload, id);
+        }
+        self.nodes.push(payload);
+        self.outgoing.push(Vec::new());
+        if Ty::is_directed() {
+            self.incoming.push(Vec::new());
         }
-        return None;
+        id
     }
 
-    pub fn check_if_node_exist(&self, node: &RefNode) -> bool {
-        self.nodes.contains(node)
+    pub fn node(&self, id: NodeId) -> Option<&N> {
+        self.nodes.get(id.0)
     }
 
-    // if you add an edge with the same from and to node, it overwrites the weight
-    // NEEDS OPTIMIZATION
-    pub fn add_edge(&mut self, from: &RefNode, to: &RefNode, weight: u32) {
-        if !self.edges.contains_key(from) {
-            self.edges.insert(
-                Rc::clone(from),
-                vec![Edge::from(Rc::clone(from), Rc::clone(to), weight)],
-            );
-        } else {
-            let edge_vec = self
-                .edges
-                .get_mut(from)
-                .expect("failed to unwrap vec in add_edge");
-            // if it finds this edge already exist, it overwrites it's weight and returns
-            for edge in edge_vec.iter_mut() {
-                if &edge.to == to {
-                    edge.weight = weight;
-                    return;
-                }
+    pub fn node_mut(&mut self, id: NodeId) -> Option<&mut N> {
+        if id.0 < self.nodes.len() {
+            if let Some(index) = &mut self.spatial {
+                index.detach(&self.nodes, id);
             }
-            edge_vec.push(Edge::from(Rc::clone(from), Rc::clone(to), weight))
         }
-        if !self.edges.contains_key(to) {
-            self.edges.insert(
-                Rc::clone(to),
-                vec![Edge::from(Rc::clone(to), Rc::clone(from), weight)],
-            );
+        self.nodes.get_mut(id.0)
+    }
+
+    pub fn edge(&self, id: EdgeId) -> Option<&Edge<E>> {
+        self.edges.get(id.0)
+    }
+
+    // only the weight can change in place, moving an edge's ends has to go through remove_edge and add_edge
+    pub fn edge_weight_mut(&mut self, id: EdgeId) -> Option<&mut E> {
+        self.edges.get_mut(id.0).map(|edge| &mut edge.weight)
+    }
+
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.nodes.len()).map(NodeId)
+    }
+
+    pub fn edge_ids(&self) -> impl Iterator<Item = EdgeId> {
+        (0..self.edges.len()).map(EdgeId)
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &N)> {
+        self.nodes.iter().enumerate().map(|(i, n)| (NodeId(i), n))
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (EdgeId, &Edge<E>)> {
+        self.edges.iter().enumerate().map(|(i, e)| (EdgeId(i), e))
+    }
+
+    // edges that can be walked out of a node, oriented so `from` is the given node
+    pub fn edges_from(&self, id: NodeId) -> impl Iterator<Item = EdgeRef<'_, E>> {
+        self.outgoing[id.0].iter().map(move |&e| {
+            let edge = &self.edges[e.0];
+            let to = if edge.from == id { edge.to } else { edge.from };
+            EdgeRef {
+                id: e,
+                from: id,
+                to,
+                weight: &edge.weight,
+            }
+        })
+    }
+
+    // edges that can be walked into a node, oriented so `to` is the given node
+    pub fn edges_to(&self, id: NodeId) -> impl Iterator<Item = EdgeRef<'_, E>> {
+        let list = if Ty::is_directed() {
+            &self.incoming[id.0]
         } else {
-            let edge_vec = self
-                .edges
-                .get_mut(to)
-                .expect("failed to unwrap vec in add_edge");
-            // if it finds this edge already exist, it overwrites it's weight and returns
-            for edge in edge_vec.iter_mut() {
-                if &edge.from == from {
-                    edge.weight = weight;
-                    return;
-                }
+            &self.outgoing[id.0]
+        };
+        list.iter().map(move |&e| {
+            let edge = &self.edges[e.0];
+            let from = if edge.to == id { edge.from } else { edge.to };
+            EdgeRef {
+                id: e,
+                from,
+                to: id,
+                weight: &edge.weight,
             }
-            edge_vec.push(Edge::from(Rc::clone(to), Rc::clone(from), weight))
+        })
+    }
+
+    // nodes reachable over one edge
+    pub fn neighbors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges_from(id).map(|e| e.to)
+    }
+
+    // nodes that have an edge leading into this one
+    pub fn predecessors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges_to(id).map(|e| e.from)
+    }
+
+    // nodes sharing an edge with id either way round, for algorithms that ignore direction
+    fn linked_nodes(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let incoming = if Ty::is_directed() {
+            Some(self.predecessors(id))
+        } else {
+            None
+        };
+        self.neighbors(id).chain(incoming.into_iter().flatten())
+    }
+
+    pub fn out_degree(&self, id: NodeId) -> usize {
+        self.outgoing[id.0].len()
+    }
+
+    pub fn in_degree(&self, id: NodeId) -> usize {
+        if Ty::is_directed() {
+            self.incoming[id.0].len()
+        } else {
+            self.outgoing[id.0].len()
         }
     }
 
+    pub fn find_node<F: Fn(&N) -> bool>(&self, predicate: F) -> Option<NodeId> {
+        self.nodes.iter().position(predicate).map(NodeId)
+    }
+
+    pub fn check_if_node_exist(&self, id: NodeId) -> bool {
+        id.0 < self.nodes.len()
+    }
+
+    // in an undirected graph the order of from and to doesn't matter
+    pub fn find_edge(&self, from: NodeId, to: NodeId) -> Option<EdgeId> {
+        self.edges_from(from).find(|e| e.to == to).map(|e| e.id)
+    }
+
+    // adds an edge, one way for directed graphs and both ways for undirected ones
     // if you add an edge with the same from and to node, it overwrites the weight
-    pub fn add_edge_by_index(&mut self, from: usize, to: usize, weight: u32) {
-        let from_node = self.nodes.get(from).expect("Failed to get from node");
-        let to_node = self.nodes.get(to).expect("Failed to get to node");
-        if !self.edges.contains_key(from_node) {
-            self.edges.insert(
-                Rc::clone(from_node),
-                vec![Edge::from(Rc::clone(from_node), Rc::clone(to_node), weight)],
-            );
-        } else {
-            let edge_vec = self
-                .edges
-                .get_mut(from_node)
-                .expect("failed to unwrap vec in add_edge");
-            // if it finds this edge already exist, it overwrites it's weight and returns
-            for edge in edge_vec.iter_mut() {
-                if &edge.to == to_node {
-                    edge.weight = weight;
-                    return;
-                }
-            }
-            edge_vec.push(Edge::from(Rc::clone(from_node), Rc::clone(to_node), weight))
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: E) -> EdgeId {
+        assert!(self.check_if_node_exist(from), "Failed to get from node");
+        assert!(self.check_if_node_exist(to), "Failed to get to node");
+        if let Some(existing) = self.find_edge(from, to) {
+            self.edges[existing.0].weight = weight;
+            return existing;
         }
+        let id = EdgeId(self.edges.len());
+        self.edges.push(Edge::from(from, to, weight));
+        self.outgoing[from.0].push(id);
+        if Ty::is_directed() {
+            self.incoming[to.0].push(id);
+        } else if from != to {
+            self.outgoing[to.0].push(id);
+        }
+        id
     }
 
-    // Breath first tree traversal
-    pub fn bft(&self, start: &RefNode) -> Option<Vec<RefNode>> {
-        if self.check_if_node_exist(start) {
-            let mut vec_to_return: Vec<RefNode> = Vec::new();
-
-            // hashmap of what nots have been visited
-            let mut visited: HashMap<&RefNode, bool> = HashMap::new();
-            for node in self.nodes.iter() {
-                visited.insert(node, false);
+    // same as add_edge but takes the nodes' indices
+    pub fn add_edge_by_index(&mut self, from: usize, to: usize, weight: E) -> EdgeId {
+        self.add_edge(NodeId(from), NodeId(to), weight)
+    }
+
+    // swaps old for new in the adjacency lists the edge stored at `at` shows up in, None drops it
+    fn relink_edge(&mut self, at: EdgeId, old: EdgeId, new: Option<EdgeId>) {
+        let (from, to) = (self.edges[at.0].from, self.edges[at.0].to);
+        relink(&mut self.outgoing[from.0], old, new);
+        if Ty::is_directed() {
+            relink(&mut self.incoming[to.0], old, new);
+        } else if from != to {
+            relink(&mut self.outgoing[to.0], old, new);
+        }
+    }
+
+    // removes an edge and returns it
+    // the last edge is moved into the freed slot, so the edge with id edge_count() - 1 takes this id
+    pub fn remove_edge(&mut self, id: EdgeId) -> Option<Edge<E>> {
+        if id.0 >= self.edges.len() {
+            return None;
+        }
+        self.relink_edge(id, id, None);
+        let removed = self.edges.swap_remove(id.0);
+        if id.0 < self.edges.len() {
+            self.relink_edge(id, EdgeId(self.edges.len()), Some(id));
+        }
+        Some(removed)
+    }
+
+    // removes a node with all its edges and returns its payload
+    // the last node is moved into the freed slot, so the node with id node_count() - 1 takes this id
+    // edges get moved around the same way remove_edge moves them
+    pub fn remove_node(&mut self, id: NodeId) -> Option<N> {
+        if !self.check_if_node_exist(id) {
+            return None;
+        }
+        if let Some(index) = &mut self.spatial {
+            index.settle(&self.nodes);
+        }
+        while let Some(&edge) = self.outgoing[id.0].last() {
+            self.remove_edge(edge);
+        }
+        if Ty::is_directed() {
+            while let Some(&edge) = self.incoming[id.0].last() {
+                self.remove_edge(edge);
             }
+        }
 
-            let mut queue: VecDeque<&RefNode> = VecDeque::new();
-
-            *visited.get_mut(start).unwrap() = true;
-            queue.push_back(start);
-
-            while queue.len() > 0 {
-                // Dequeue the front of the queue
-                let current_node = queue.pop_front().unwrap();
-                vec_to_return.push(Rc::clone(current_node));
-
-                // get all the adj vertices of that node
-                let adjs = self.edges.get(current_node);
-                match adjs {
-                    None => {}
-                    Some(adjs) => {
-                        for adj in adjs.iter() {
-                            if !visited.get(&adj.to).unwrap() {
-                                *visited.get_mut(&adj.to).unwrap() = true;
-                                queue.push_back(&adj.to);
-                            }
-                        }
-                    }
+        let removed = self.nodes.swap_remove(id.0);
+        if let Some(index) = &mut self.spatial {
+            index.remove(&removed, id);
+            if id.0 < self.nodes.len() {
+                index.moved(&self.nodes[id.0], NodeId(self.nodes.len()), id);
+            }
+        }
+        self.outgoing.swap_remove(id.0);
+        if Ty::is_directed() {
+            self.incoming.swap_remove(id.0);
+        }
+        // edges of the node that got moved need their endpoints renamed
+        if id.0 < self.nodes.len() {
+            let moved = NodeId(self.nodes.len());
+            let mut touched = self.outgoing[id.0].clone();
+            if Ty::is_directed() {
+                touched.extend_from_slice(&self.incoming[id.0]);
+            }
+            for edge in touched {
+                let edge = &mut self.edges[edge.0];
+                if edge.from == moved {
+                    edge.from = id;
+                }
+                if edge.to == moved {
+                    edge.to = id;
                 }
             }
-            Some(vec_to_return)
-        } else {
-            None
         }
+        Some(removed)
     }
 
-    pub fn dft(&self, start: &RefNode) -> Option<Vec<RefNode>> {
-        if self.check_if_node_exist(start) {
-            let mut vec_to_return: Vec<RefNode> = Vec::new();
-
-            let mut visited: HashMap<&RefNode, bool> = HashMap::new();
-            for node in self.nodes.iter() {
-                visited.insert(node, false);
+    // keeps only the nodes the predicate returns true for, along with the edges between them
+    // ids of the surviving nodes can change, see remove_node
+    pub fn retain_nodes<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(NodeId, &N) -> bool,
+    {
+        // going from the back means a node moved into a freed slot has already been checked
+        for i in (0..self.nodes.len()).rev() {
+            if !keep(NodeId(i), &self.nodes[i]) {
+                self.remove_node(NodeId(i));
             }
+        }
+    }
 
-            let mut stack = Vec::new();
-            stack.push(start);
-
-            while stack.len() > 0 {
-                let current_node = stack.pop().unwrap();
-                if !visited.get(&current_node).unwrap() {
-                    vec_to_return.push(Rc::clone(current_node));
-                    *visited.get_mut(&current_node).unwrap() = true;
-                    match self.edges.get(current_node) {
-                        None => {}
-                        Some(edges) => {
-                            for edge in edges {
-                                stack.push(&edge.to);
-                            }
-                        }
-                    }
-                }
+    // keeps only the edges the predicate returns true for
+    // ids of the surviving edges can change, see remove_edge
+    pub fn retain_edges<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(EdgeId, &Edge<E>) -> bool,
+    {
+        for i in (0..self.edges.len()).rev() {
+            if !keep(EdgeId(i), &self.edges[i]) {
+                self.remove_edge(EdgeId(i));
             }
+        }
+    }
 
-            Some(vec_to_return)
-        } else {
-            None
+    // Breath first tree traversal
+    pub fn bft(&self, start: NodeId) -> Option<Vec<NodeId>> {
+        if !self.check_if_node_exist(start) {
+            return None;
         }
+        Some(self.bfs(start).collect())
+    }
+
+    // Depth first tree traversal
+    pub fn dft(&self, start: NodeId) -> Option<Vec<NodeId>> {
+        if !self.check_if_node_exist(start) {
+            return None;
+        }
+        Some(self.dfs(start).collect())
+    }
+}
+
+impl<N, E, Ty: EdgeType> Adjacency for Graph<N, E, Ty> {
+    type Weight = E;
+
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn edges_from(&self, id: NodeId) -> impl Iterator<Item = EdgeRef<'_, E>> {
+        Graph::edges_from(self, id)
     }
 
+    fn edges_to(&self, id: NodeId) -> impl Iterator<Item = EdgeRef<'_, E>> {
+        Graph::edges_to(self, id)
+    }
+}
+
+impl<E: std::fmt::Display, Ty: EdgeType> Graph<Node, E, Ty> {
     pub fn print(&self) {
         for (index, node) in self.nodes.iter().enumerate() {
             print!("{}: {},{}: ", index, node.x, node.y);
-            if self.edges.contains_key(node) {
-                for edge in self.edges.get(node).expect("Failed to unwrap edges") {
-                    print!(
-                        "{},{}->({}){},{} ",
-                        edge.from.x, edge.from.y, edge.weight, edge.to.x, edge.to.y
-                    );
-                }
+            for edge in self.edges_from(NodeId(index)) {
+                let from = &self[edge.from];
+                let to = &self[edge.to];
+                print!("{},{}->({}){},{} ", from.x, from.y, edge.weight, to.x, to.y);
             }
             println!();
         }
     }
 }
+
+fn relink(list: &mut Vec<EdgeId>, old: EdgeId, new: Option<EdgeId>) {
+    match new {
+        None => list.retain(|&e| e != old),
+        Some(new) => {
+            for e in list.iter_mut() {
+                if *e == old {
+                    *e = new;
+                }
+            }
+        }
+    }
+}
+
+impl<N, E, Ty> Index<NodeId> for Graph<N, E, Ty> {
+    type Output = N;
+    fn index(&self, id: NodeId) -> &N {
+        &self.nodes[id.0]
+    }
+}
+
+impl<N, E, Ty> IndexMut<NodeId> for Graph<N, E, Ty> {
+    fn index_mut(&mut self, id: NodeId) -> &mut N {
+        if let Some(index) = &mut self.spatial {
+            index.detach(&self.nodes, id);
+        }
+        &mut self.nodes[id.0]
+    }
+}
+
+impl<N, E, Ty> Index<EdgeId> for Graph<N, E, Ty> {
+    type Output = Edge<E>;
+    fn index(&self, id: EdgeId) -> &Edge<E> {
+        &self.edges[id.0]
+    }
+}
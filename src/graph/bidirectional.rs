@@ -0,0 +1,274 @@
+use super::{Adjacency, Csr, EdgeType, Graph, NodeId, Path, Weight};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// one half of a bidirectional search, the backward half walks edges against their direction
+struct Side<D> {
+    distances: Vec<Option<D>>,
+    parents: Vec<Option<NodeId>>,
+}
+
+impl<D: Copy> Side<D> {
+    fn new(start: NodeId, node_count: usize, zero: D) -> Side<D> {
+        let mut distances = vec![None; node_count];
+        distances[start.index()] = Some(zero);
+        Side {
+            distances,
+            parents: vec![None; node_count],
+        }
+    }
+
+    fn distance(&self, node: NodeId) -> Option<D> {
+        self.distances[node.index()]
+    }
+
+    // nodes from node back to where this side started, node first
+    fn chain(&self, node: NodeId) -> Vec<NodeId> {
+        let mut nodes = vec![node];
+        let mut current = node;
+        while let Some(parent) = self.parents[current.index()] {
+            nodes.push(parent);
+            current = parent;
+        }
+        nodes
+    }
+}
+
+// where the two searches touched, an edge from a node the forward side reached to one the backward side reached
+struct Meeting<D> {
+    cost: D,
+    from: NodeId,
+    to: NodeId,
+}
+
+fn join<D: Copy>(forward: &Side<D>, backward: &Side<D>, meeting: &Meeting<D>) -> Vec<NodeId> {
+    let mut nodes = forward.chain(meeting.from);
+    nodes.reverse();
+    if meeting.from != meeting.to {
+        nodes.extend(backward.chain(meeting.to));
+    }
+    nodes
+}
+
+// fewest edges path, grows whichever frontier is smaller one whole level at a time
+fn bidirectional_bfs<G: Adjacency>(
+    graph: &G,
+    source: NodeId,
+    target: NodeId,
+) -> Option<Vec<NodeId>> {
+    if !graph.contains_node(source) || !graph.contains_node(target) {
+        return None;
+    }
+    if source == target {
+        return Some(vec![source]);
+    }
+    let mut forward = Side::new(source, graph.node_count(), 0);
+    let mut backward = Side::new(target, graph.node_count(), 0);
+    let mut forward_frontier = vec![source];
+    let mut backward_frontier = vec![target];
+
+    while !forward_frontier.is_empty() && !backward_frontier.is_empty() {
+        let meeting = if forward_frontier.len() <= backward_frontier.len() {
+            bfs_level(&mut forward, &backward, &mut forward_frontier, |n| {
+                graph.edges_from(n).map(|e| e.to)
+            })
+        } else {
+            let meeting = bfs_level(&mut backward, &forward, &mut backward_frontier, |n| {
+                graph.edges_to(n).map(|e| e.from)
+            });
+            // the backward side found it walking against the edge, so flip it
+            meeting.map(|m| Meeting {
+                to: m.from,
+                from: m.to,
+                cost: m.cost,
+            })
+        };
+        if let Some(meeting) = meeting {
+            return Some(join(&forward, &backward, &meeting));
+        }
+    }
+    None
+}
+
+// replaces frontier with the next level, returns the shortest way across if the other side was touched
+// every node on a level is as far out as the others, but the nodes it touches on the other side aren't
+// so the whole level is looked at before picking
+fn bfs_level<F, I>(
+    this: &mut Side<usize>,
+    other: &Side<usize>,
+    frontier: &mut Vec<NodeId>,
+    next: F,
+) -> Option<Meeting<usize>>
+where
+    F: Fn(NodeId) -> I,
+    I: Iterator<Item = NodeId>,
+{
+    let mut best: Option<Meeting<usize>> = None;
+    let mut level = Vec::new();
+    for &node in frontier.iter() {
+        let depth = this.distances[node.index()].unwrap() + 1;
+        for neighbor in next(node) {
+            if let Some(rest) = other.distance(neighbor) {
+                if best.as_ref().is_none_or(|b| depth + rest < b.cost) {
+                    best = Some(Meeting {
+                        cost: depth + rest,
+                        from: node,
+                        to: neighbor,
+                    });
+                }
+            }
+            if this.distances[neighbor.index()].is_none() {
+                this.distances[neighbor.index()] = Some(depth);
+                this.parents[neighbor.index()] = Some(node);
+                level.push(neighbor);
+            }
+        }
+    }
+    *frontier = level;
+    best
+}
+
+// dijkstra from both ends at once, each step settles a node on whichever side has the closer one
+// stops once the two closest unsettled nodes together cost at least as much as the best path found
+fn bidirectional_dijkstra<G>(graph: &G, source: NodeId, target: NodeId) -> Option<Path<G::Weight>>
+where
+    G: Adjacency,
+    G::Weight: Weight,
+{
+    if !graph.contains_node(source) || !graph.contains_node(target) {
+        return None;
+    }
+    let zero = G::Weight::zero();
+    if source == target {
+        return Some(Path {
+            nodes: vec![source],
+            cost: zero,
+        });
+    }
+    let mut forward = Side::new(source, graph.node_count(), zero);
+    let mut backward = Side::new(target, graph.node_count(), zero);
+    let mut forward_heap = BinaryHeap::new();
+    let mut backward_heap = BinaryHeap::new();
+    forward_heap.push(Reverse((zero, source)));
+    backward_heap.push(Reverse((zero, target)));
+    let mut best: Option<Meeting<G::Weight>> = None;
+
+    while let (Some(&Reverse((f, _))), Some(&Reverse((b, _)))) =
+        (forward_heap.peek(), backward_heap.peek())
+    {
+        if best.as_ref().is_some_and(|best| f + b >= best.cost) {
+            break;
+        }
+        if f <= b {
+            let Reverse((cost, node)) = forward_heap.pop().unwrap();
+            let edges = graph.edges_from(node).map(|e| (e.to, *e.weight));
+            if let Some(meeting) = relax(
+                &mut forward,
+                &backward,
+                &mut forward_heap,
+                node,
+                cost,
+                edges,
+            ) {
+                if best.as_ref().is_none_or(|best| meeting.cost < best.cost) {
+                    best = Some(meeting);
+                }
+            }
+        } else {
+            let Reverse((cost, node)) = backward_heap.pop().unwrap();
+            let edges = graph.edges_to(node).map(|e| (e.from, *e.weight));
+            if let Some(meeting) = relax(
+                &mut backward,
+                &forward,
+                &mut backward_heap,
+                node,
+                cost,
+                edges,
+            ) {
+                if best.as_ref().is_none_or(|best| meeting.cost < best.cost) {
+                    best = Some(Meeting {
+                        cost: meeting.cost,
+                        from: meeting.to,
+                        to: meeting.from,
+                    });
+                }
+            }
+        }
+    }
+
+    let best = best?;
+    Some(Path {
+        nodes: join(&forward, &backward, &best),
+        cost: best.cost,
+    })
+}
+
+// one dijkstra step for a side, returns the cheapest way across to the other side through node's edges
+fn relax<E, I>(
+    this: &mut Side<E>,
+    other: &Side<E>,
+    heap: &mut BinaryHeap<Reverse<(E, NodeId)>>,
+    node: NodeId,
+    cost: E,
+    edges: I,
+) -> Option<Meeting<E>>
+where
+    E: Weight,
+    I: Iterator<Item = (NodeId, E)>,
+{
+    // a cheaper way to this node was found after this entry was pushed
+    if this.distance(node) != Some(cost) {
+        return None;
+    }
+    let mut best: Option<Meeting<E>> = None;
+    for (next, weight) in edges {
+        let next_cost = cost + weight;
+        if let Some(rest) = other.distance(next) {
+            if best.as_ref().is_none_or(|b| next_cost + rest < b.cost) {
+                best = Some(Meeting {
+                    cost: next_cost + rest,
+                    from: node,
+                    to: next,
+                });
+            }
+        }
+        let better = match this.distances[next.index()] {
+            None => true,
+            Some(current) => next_cost < current,
+        };
+        if better {
+            this.distances[next.index()] = Some(next_cost);
+            this.parents[next.index()] = Some(node);
+            heap.push(Reverse((next_cost, next)));
+        }
+    }
+    best
+}
+
+impl<N, E, Ty: EdgeType> Graph<N, E, Ty> {
+    // path with the fewest edges, searched from both ends until the two meet
+    pub fn bidirectional_bfs(&self, source: NodeId, target: NodeId) -> Option<Vec<NodeId>> {
+        bidirectional_bfs(self, source, target)
+    }
+}
+
+impl<N, E: Weight, Ty: EdgeType> Graph<N, E, Ty> {
+    // same answer as dijkstra, usually settling far fewer nodes on big graphs
+    pub fn bidirectional_dijkstra(&self, source: NodeId, target: NodeId) -> Option<Path<E>> {
+        bidirectional_dijkstra(self, source, target)
+    }
+}
+
+impl<N, E, Ty: EdgeType> Csr<N, E, Ty> {
+    // see Graph::bidirectional_bfs
+    pub fn bidirectional_bfs(&self, source: NodeId, target: NodeId) -> Option<Vec<NodeId>> {
+        bidirectional_bfs(self, source, target)
+    }
+}
+
+impl<N, E: Weight, Ty: EdgeType> Csr<N, E, Ty> {
+    // see Graph::bidirectional_dijkstra
+    pub fn bidirectional_dijkstra(&self, source: NodeId, target: NodeId) -> Option<Path<E>> {
+        bidirectional_dijkstra(self, source, target)
+    }
+}
@@ -0,0 +1,80 @@
+use super::{EdgeId, Graph, NodeId, Undirected};
+
+impl<N, E> Graph<N, E, Undirected> {
+    // edges whose removal splits their component in two
+    pub fn bridges(&self) -> Vec<EdgeId> {
+        self.low_link().0
+    }
+
+    // nodes whose removal splits their component, also called cut vertices
+    pub fn articulation_points(&self) -> Vec<NodeId> {
+        self.low_link().1
+    }
+
+    // Tarjan's low link dfs, finds bridges and articulation points in one pass
+    // iterative so long corridors don't overflow the stack
+    fn low_link(&self) -> (Vec<EdgeId>, Vec<NodeId>) {
+        let n = self.node_count();
+        let mut discovered = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut is_cut = vec![false; n];
+        let mut bridges = Vec::new();
+        let mut timer = 0;
+        // node, position in its edges, edge used to get there
+        let mut call: Vec<(usize, usize, Option<EdgeId>)> = Vec::new();
+
+        for root in 0..n {
+            if discovered[root] != usize::MAX {
+                continue;
+            }
+            discovered[root] = timer;
+            low[root] = timer;
+            timer += 1;
+            let mut root_children = 0;
+            call.push((root, 0, None));
+
+            while let Some((v, pos, parent_edge)) = call.last_mut() {
+                let v = *v;
+                let parent_edge = *parent_edge;
+                if *pos < self.outgoing[v].len() {
+                    let edge = self.outgoing[v][*pos];
+                    *pos += 1;
+                    if Some(edge) == parent_edge {
+                        continue;
+                    }
+                    let e = &self.edges[edge.0];
+                    let w = if e.from.0 == v { e.to.0 } else { e.from.0 };
+                    if discovered[w] == usize::MAX {
+                        discovered[w] = timer;
+                        low[w] = timer;
+                        timer += 1;
+                        if v == root {
+                            root_children += 1;
+                        }
+                        call.push((w, 0, Some(edge)));
+                    } else {
+                        low[v] = low[v].min(discovered[w]);
+                    }
+                    continue;
+                }
+
+                call.pop();
+                if let Some(&(parent, _, _)) = call.last() {
+                    low[parent] = low[parent].min(low[v]);
+                    if low[v] > discovered[parent] {
+                        bridges.push(parent_edge.expect("tree edge missing"));
+                    }
+                    if parent != root && low[v] >= discovered[parent] {
+                        is_cut[parent] = true;
+                    }
+                }
+            }
+            if root_children > 1 {
+                is_cut[root] = true;
+            }
+        }
+
+        let cut_nodes = (0..n).filter(|&i| is_cut[i]).map(NodeId).collect();
+        (bridges, cut_nodes)
+    }
+}
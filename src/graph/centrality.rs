@@ -0,0 +1,252 @@
+use super::{Directed, EdgeType, Graph, NodeId, Weight};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+// what one single source pass of Brandes' algorithm leaves behind
+struct ShortestPathDag {
+    // nodes in the order their distance was fixed
+    order: Vec<NodeId>,
+    // nodes right before each node on some shortest path from the source
+    parents: Vec<Vec<NodeId>>,
+    // how many shortest paths from the source end at each node
+    counts: Vec<f64>,
+}
+
+impl ShortestPathDag {
+    fn new(node_count: usize) -> ShortestPathDag {
+        ShortestPathDag {
+            order: Vec::with_capacity(node_count),
+            parents: vec![Vec::new(); node_count],
+            counts: vec![0.0; node_count],
+        }
+    }
+
+    fn reset(&mut self, source: NodeId) {
+        self.order.clear();
+        self.parents.iter_mut().for_each(Vec::clear);
+        self.counts.iter_mut().for_each(|count| *count = 0.0);
+        self.counts[source.index()] = 1.0;
+    }
+
+    // adds the share of the source's shortest paths each node sits on, walking back from the furthest node
+    fn accumulate(&self, dependency: &mut [f64], scores: &mut [f64]) {
+        dependency.iter_mut().for_each(|d| *d = 0.0);
+        for (i, &node) in self.order.iter().enumerate().rev() {
+            for &parent in &self.parents[node.index()] {
+                let share = self.counts[parent.index()] / self.counts[node.index()];
+                dependency[parent.index()] += share * (1.0 + dependency[node.index()]);
+            }
+            // the source is always first and doesn't count as being in between
+            if i > 0 {
+                scores[node.index()] += dependency[node.index()];
+            }
+        }
+    }
+}
+
+impl<N, E, Ty: EdgeType> Graph<N, E, Ty> {
+    // edges touching each node over the most any node could have, directed graphs count in and out edges
+    // so a directed node can have twice as many, one each way to every other node
+    pub fn degree_centrality(&self) -> Vec<f64> {
+        if self.node_count() <= 1 {
+            return vec![1.0; self.node_count()];
+        }
+        let mut scale = (self.node_count() - 1) as f64;
+        if Ty::is_directed() {
+            scale *= 2.0;
+        }
+        self.node_ids()
+            .map(|id| {
+                let degree = if Ty::is_directed() {
+                    self.out_degree(id) + self.in_degree(id)
+                } else {
+                    self.out_degree(id)
+                };
+                degree as f64 / scale
+            })
+            .collect()
+    }
+
+    // how close each node is to the nodes it can reach, counted in edges
+    // scaled by the share of the graph it reaches so nodes in small components don't look central
+    pub fn closeness_centrality(&self) -> Vec<f64> {
+        let mut depths = vec![None; self.node_count()];
+        let mut queue = VecDeque::new();
+        self.node_ids()
+            .map(|source| {
+                depths.iter_mut().for_each(|depth| *depth = None);
+                depths[source.index()] = Some(0);
+                queue.push_back(source);
+                let (mut reached, mut total) = (0, 0);
+                while let Some(node) = queue.pop_front() {
+                    let depth = depths[node.index()].unwrap();
+                    reached += 1;
+                    total += depth;
+                    for next in self.neighbors(node) {
+                        if depths[next.index()].is_none() {
+                            depths[next.index()] = Some(depth + 1);
+                            queue.push_back(next);
+                        }
+                    }
+                }
+                closeness(reached, total as f64, self.node_count())
+            })
+            .collect()
+    }
+
+    // how many shortest paths between other nodes go through each node, counted in edges
+    // a pair joined by several equally short paths splits its count between them
+    // undirected pairs are only counted once
+    pub fn betweenness_centrality(&self) -> Vec<f64> {
+        let mut depths = vec![None; self.node_count()];
+        let mut queue = VecDeque::new();
+        self.betweenness_by(|source, dag| {
+            depths.iter_mut().for_each(|depth| *depth = None);
+            depths[source.index()] = Some(0);
+            queue.push_back(source);
+            while let Some(node) = queue.pop_front() {
+                dag.order.push(node);
+                let depth = depths[node.index()].unwrap() + 1;
+                for next in self.neighbors(node) {
+                    if depths[next.index()].is_none() {
+                        depths[next.index()] = Some(depth);
+                        queue.push_back(next);
+                    }
+                    if depths[next.index()] == Some(depth) {
+                        dag.counts[next.index()] += dag.counts[node.index()];
+                        dag.parents[next.index()].push(node);
+                    }
+                }
+            }
+        })
+    }
+
+    // single_source fills in the dag for one source, it starts out reset for that source
+    fn betweenness_by<F>(&self, mut single_source: F) -> Vec<f64>
+    where
+        F: FnMut(NodeId, &mut ShortestPathDag),
+    {
+        let mut dag = ShortestPathDag::new(self.node_count());
+        let mut scores = vec![0.0; self.node_count()];
+        let mut dependency = vec![0.0; self.node_count()];
+        for source in self.node_ids() {
+            dag.reset(source);
+            single_source(source, &mut dag);
+            dag.accumulate(&mut dependency, &mut scores);
+        }
+        if !Ty::is_directed() {
+            // every undirected pair was walked from both ends
+            scores.iter_mut().for_each(|score| *score /= 2.0);
+        }
+        scores
+    }
+}
+
+impl<N, E: Weight, Ty: EdgeType> Graph<N, E, Ty> {
+    // closeness_centrality with distances summed from the edge weights
+    pub fn weighted_closeness_centrality(&self) -> Vec<f64> {
+        self.node_ids()
+            .map(|source| {
+                let paths = self.dijkstra_all(source).unwrap();
+                let (mut reached, mut total) = (0, 0.0);
+                for node in self.node_ids() {
+                    if let Some(distance) = paths.distance(node) {
+                        reached += 1;
+                        total += distance.as_f64();
+                    }
+                }
+                closeness(reached, total, self.node_count())
+            })
+            .collect()
+    }
+
+    // betweenness_centrality where shortest means cheapest by edge weight
+    pub fn weighted_betweenness_centrality(&self) -> Vec<f64> {
+        let mut distances = vec![None; self.node_count()];
+        let mut settled = vec![false; self.node_count()];
+        let mut heap = BinaryHeap::new();
+        self.betweenness_by(|source, dag| {
+            distances.iter_mut().for_each(|distance| *distance = None);
+            settled.iter_mut().for_each(|done| *done = false);
+            distances[source.index()] = Some(E::zero());
+            heap.push(Reverse((E::zero(), source)));
+            while let Some(Reverse((cost, node))) = heap.pop() {
+                if settled[node.index()] {
+                    continue;
+                }
+                settled[node.index()] = true;
+                dag.order.push(node);
+                for edge in self.edges_from(node) {
+                    let next = edge.to.index();
+                    // zero weight edges can lead back to nodes already settled at the same cost
+                    if settled[next] {
+                        continue;
+                    }
+                    let next_cost = cost + *edge.weight;
+                    match distances[next] {
+                        Some(current) if next_cost > current => {}
+                        Some(current) if next_cost == current => {
+                            dag.counts[next] += dag.counts[node.index()];
+                            dag.parents[next].push(node);
+                        }
+                        _ => {
+                            distances[next] = Some(next_cost);
+                            dag.counts[next] = dag.counts[node.index()];
+                            dag.parents[next].clear();
+                            dag.parents[next].push(node);
+                            heap.push(Reverse((next_cost, edge.to)));
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+impl<N, E> Graph<N, E, Directed> {
+    // share of time a random walker following the edges spends on each node, the scores add up to 1
+    // with probability 1 - damping it jumps to a random node instead, so does a walker on a node with no way out
+    // stops after `iterations` rounds or once the scores stop changing, edge weights are ignored
+    pub fn page_rank(&self, damping: f64, iterations: usize) -> Vec<f64> {
+        let n = self.node_count();
+        if n == 0 {
+            return Vec::new();
+        }
+        let mut ranks = vec![1.0 / n as f64; n];
+        let mut next = vec![0.0; n];
+        for _ in 0..iterations {
+            let stranded: f64 = self
+                .node_ids()
+                .filter(|&id| self.out_degree(id) == 0)
+                .map(|id| ranks[id.index()])
+                .sum();
+            let base = (1.0 - damping) / n as f64 + damping * stranded / n as f64;
+            next.iter_mut().for_each(|rank| *rank = base);
+            for id in self.node_ids() {
+                let degree = self.out_degree(id);
+                if degree == 0 {
+                    continue;
+                }
+                let share = damping * ranks[id.index()] / degree as f64;
+                for to in self.neighbors(id) {
+                    next[to.index()] += share;
+                }
+            }
+            let change: f64 = ranks.iter().zip(&next).map(|(a, b)| (a - b).abs()).sum();
+            std::mem::swap(&mut ranks, &mut next);
+            if change < 1e-12 {
+                break;
+            }
+        }
+        ranks
+    }
+}
+
+// (reached - 1) / total, scaled by (reached - 1) / (node_count - 1)
+fn closeness(reached: usize, total: f64, node_count: usize) -> f64 {
+    if total <= 0.0 || node_count <= 1 {
+        return 0.0;
+    }
+    let others = (reached - 1) as f64;
+    others / total * others / (node_count - 1) as f64
+}
@@ -0,0 +1,174 @@
+use super::{EdgeType, Graph, NodeId};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+// a color for every node so that no edge joins two nodes of the same color, colors run from 0
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Coloring {
+    colors: Vec<usize>,
+    count: usize,
+}
+
+impl Coloring {
+    pub fn color_of(&self, node: NodeId) -> usize {
+        self.colors[node.index()]
+    }
+
+    // color of every node, indexed by node index
+    pub fn colors(&self) -> &[usize] {
+        &self.colors
+    }
+
+    // number of different colors used
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn nodes_with(&self, color: usize) -> Vec<NodeId> {
+        self.colors
+            .iter()
+            .enumerate()
+            .filter(|(_, &c)| c == color)
+            .map(|(i, _)| NodeId::new(i))
+            .collect()
+    }
+}
+
+// which node greedy_coloring colors first
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ColoringOrder {
+    // by node index
+    NodeOrder,
+    // most neighbors first
+    LargestFirst,
+    // repeatedly takes out the node with the fewest neighbors left and colors in reverse,
+    // never needs more colors than one plus the largest number of neighbors left at removal
+    SmallestLast,
+    // the given nodes in that order, nodes left out follow by index
+    Custom(Vec<NodeId>),
+}
+
+impl<N, E, Ty: EdgeType> Graph<N, E, Ty> {
+    // gives each node in turn the smallest color none of its neighbors has yet
+    // edge direction is ignored and self loops don't count
+    pub fn greedy_coloring(&self, order: ColoringOrder) -> Coloring {
+        let adjacency = self.simple_adjacency();
+        let order: Vec<usize> = match order {
+            ColoringOrder::NodeOrder => (0..self.node_count()).collect(),
+            ColoringOrder::LargestFirst => {
+                let mut order: Vec<usize> = (0..self.node_count()).collect();
+                order.sort_by_key(|&i| Reverse(adjacency[i].len()));
+                order
+            }
+            ColoringOrder::SmallestLast => smallest_last(&adjacency),
+            ColoringOrder::Custom(nodes) => {
+                let mut listed = vec![false; self.node_count()];
+                let mut order = Vec::with_capacity(self.node_count());
+                for node in nodes {
+                    if self.check_if_node_exist(node) && !listed[node.index()] {
+                        listed[node.index()] = true;
+                        order.push(node.index());
+                    }
+                }
+                order.extend((0..self.node_count()).filter(|&i| !listed[i]));
+                order
+            }
+        };
+
+        let mut colors = vec![usize::MAX; self.node_count()];
+        // taken[c] == node means a neighbor of node already has color c
+        let mut taken = vec![usize::MAX; self.node_count() + 1];
+        let mut count = 0;
+        for node in order {
+            for &next in &adjacency[node] {
+                if colors[next] != usize::MAX {
+                    taken[colors[next]] = node;
+                }
+            }
+            let color = (0..).find(|&c| taken[c] != node).unwrap();
+            colors[node] = color;
+            count = count.max(color + 1);
+        }
+        Coloring { colors, count }
+    }
+
+    // DSATUR, always colors the node whose neighbors already use the most different colors next
+    // ties go to the node with the most neighbors, then the lowest index
+    pub fn dsatur_coloring(&self) -> Coloring {
+        let adjacency = self.simple_adjacency();
+        let mut colors = vec![usize::MAX; self.node_count()];
+        let mut neighbor_colors: Vec<HashSet<usize>> = vec![HashSet::new(); self.node_count()];
+        let mut heap: BinaryHeap<(usize, usize, Reverse<usize>)> = (0..self.node_count())
+            .map(|i| (0, adjacency[i].len(), Reverse(i)))
+            .collect();
+        let mut count = 0;
+
+        while let Some((saturation, _, Reverse(node))) = heap.pop() {
+            // stale entry, the node was colored or its saturation went up since it was pushed
+            if colors[node] != usize::MAX || saturation != neighbor_colors[node].len() {
+                continue;
+            }
+            let color = (0..).find(|c| !neighbor_colors[node].contains(c)).unwrap();
+            colors[node] = color;
+            count = count.max(color + 1);
+            for &next in &adjacency[node] {
+                if colors[next] == usize::MAX && neighbor_colors[next].insert(color) {
+                    let saturation = neighbor_colors[next].len();
+                    heap.push((saturation, adjacency[next].len(), Reverse(next)));
+                }
+            }
+        }
+        Coloring { colors, count }
+    }
+
+    // distinct neighbors of every node, ignoring direction and self loops
+    fn simple_adjacency(&self) -> Vec<Vec<usize>> {
+        self.node_ids()
+            .map(|id| {
+                let mut list: Vec<usize> = self
+                    .linked_nodes(id)
+                    .filter(|&next| next != id)
+                    .map(NodeId::index)
+                    .collect();
+                list.sort_unstable();
+                list.dedup();
+                list
+            })
+            .collect()
+    }
+}
+
+// smallest last order using buckets of nodes by how many neighbors they have left
+fn smallest_last(adjacency: &[Vec<usize>]) -> Vec<usize> {
+    let n = adjacency.len();
+    let mut degrees: Vec<usize> = adjacency.iter().map(Vec::len).collect();
+    let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for (node, &degree) in degrees.iter().enumerate() {
+        buckets[degree].push(node);
+    }
+    let mut removed = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+    let mut lowest: usize = 0;
+    while order.len() < n {
+        // a removal can drop a neighbor one bucket below where the search is
+        lowest = lowest.saturating_sub(1);
+        while buckets[lowest].is_empty() {
+            lowest += 1;
+        }
+        let node = buckets[lowest].pop().unwrap();
+        // stale entry, the node moved to a lower bucket or is already out
+        if removed[node] || degrees[node] != lowest {
+            continue;
+        }
+        removed[node] = true;
+        order.push(node);
+        for &next in &adjacency[node] {
+            if !removed[next] {
+                degrees[next] -= 1;
+                buckets[degrees[next]].push(next);
+            }
+        }
+    }
+    order.reverse();
+    order
+}
@@ -0,0 +1,282 @@
+use super::{Components, EdgeType, Graph, NodeId, Weight};
+use std::collections::HashMap;
+
+// label propagation gives up after this many rounds if labels keep changing
+const MAX_ROUNDS: usize = 100;
+// modularity gains closer than this count as equal, so rounding can't keep Louvain moving nodes
+const EPSILON: f64 = 1e-12;
+
+// nodes grouped into densely linked communities, ids are numbered the same way as Components
+#[derive(Clone, Debug)]
+pub struct Communities {
+    partition: Components,
+    modularity: f64,
+}
+
+impl Communities {
+    // community id of every node with sizes and members, see Components
+    pub fn partition(&self) -> &Components {
+        &self.partition
+    }
+
+    pub fn community_of(&self, node: NodeId) -> usize {
+        self.partition.component_of(node)
+    }
+
+    pub fn count(&self) -> usize {
+        self.partition.count()
+    }
+
+    pub fn modularity(&self) -> f64 {
+        self.modularity
+    }
+}
+
+impl<N, E: Weight, Ty: EdgeType> Graph<N, E, Ty> {
+    // how much more of the edge weight stays inside the groups than it would in a random graph
+    // with the same node strengths, labels[i] is the group of node i and direction is ignored
+    // 0 when the edges weigh nothing in total
+    pub fn modularity(&self, labels: &[usize]) -> f64 {
+        let mut inside = HashMap::new();
+        let mut strengths = HashMap::new();
+        let mut total = 0.0;
+        for (_, edge) in self.edges() {
+            let weight = edge.weight.as_f64();
+            let (from, to) = (labels[edge.from.index()], labels[edge.to.index()]);
+            *strengths.entry(from).or_insert(0.0) += weight;
+            *strengths.entry(to).or_insert(0.0) += weight;
+            if from == to {
+                *inside.entry(from).or_insert(0.0) += weight;
+            }
+            total += weight;
+        }
+        if total == 0.0 {
+            return 0.0;
+        }
+        let inside: f64 = inside.values().sum();
+        let expected: f64 = strengths
+            .values()
+            .map(|strength| (strength / (2.0 * total)) * (strength / (2.0 * total)))
+            .sum();
+        inside / total - expected
+    }
+
+    // every node repeatedly takes the label most of its neighbors have until nothing changes
+    // fast but rough, nodes go in a shuffled order and ties are broken at random, seed picks the run
+    // each neighbor is one vote whatever the edge weighs, only the modularity it reports is weighted
+    pub fn label_propagation(&self, seed: u64) -> Communities {
+        let mut rng = XorShift::new(seed);
+        let mut labels: Vec<usize> = (0..self.node_count()).collect();
+        let mut order: Vec<NodeId> = self.node_ids().collect();
+        let mut counts: HashMap<usize, usize> = HashMap::new();
+        let mut tied = Vec::new();
+        for _ in 0..MAX_ROUNDS {
+            rng.shuffle(&mut order);
+            let mut changed = false;
+            for &id in &order {
+                counts.clear();
+                for next in self.linked_nodes(id) {
+                    *counts.entry(labels[next.index()]).or_insert(0) += 1;
+                }
+                let most = match counts.values().max() {
+                    Some(&most) => most,
+                    None => continue,
+                };
+                let current = labels[id.index()];
+                if counts.get(&current) == Some(&most) {
+                    continue;
+                }
+                tied.clear();
+                tied.extend(counts.iter().filter(|&(_, &c)| c == most).map(|(&l, _)| l));
+                // hash order isn't fixed, so sort before picking to keep runs repeatable
+                tied.sort_unstable();
+                labels[id.index()] = tied[rng.below(tied.len())];
+                changed = true;
+            }
+            if !changed {
+                break;
+            }
+        }
+        self.communities_from(&labels)
+    }
+
+    // Louvain method, moves nodes between communities while that raises modularity
+    // then merges every community into a single node and does it again on the smaller graph
+    // heavier edges pull their ends together harder, a graph whose edges weigh nothing stays split up
+    pub fn louvain(&self) -> Communities {
+        let mut level = Level::from_graph(self);
+        // community of every original node in terms of the current level's nodes
+        let mut labels: Vec<usize> = (0..self.node_count()).collect();
+        loop {
+            let (moved, communities) = level.move_nodes();
+            if !moved {
+                break;
+            }
+            for label in labels.iter_mut() {
+                *label = communities[*label];
+            }
+            level = level.aggregate(&communities);
+        }
+        self.communities_from(&labels)
+    }
+
+    fn communities_from(&self, labels: &[usize]) -> Communities {
+        Communities {
+            partition: Components::from_raw_labels(labels),
+            modularity: self.modularity(labels),
+        }
+    }
+}
+
+// one level of Louvain, a weighted undirected graph whose nodes are the previous level's communities
+struct Level {
+    // links to other nodes, a pair can be listed more than once
+    links: Vec<Vec<(usize, f64)>>,
+    // weight of links inside the node, counted from both ends like the other links
+    loops: Vec<f64>,
+    // total weight of everything touching each node
+    degrees: Vec<f64>,
+    // twice the total link weight
+    total: f64,
+}
+
+impl Level {
+    fn from_graph<N, E: Weight, Ty: EdgeType>(graph: &Graph<N, E, Ty>) -> Level {
+        let mut links = vec![Vec::new(); graph.node_count()];
+        let mut loops = vec![0.0; graph.node_count()];
+        for (_, edge) in graph.edges() {
+            let (from, to) = (edge.from.index(), edge.to.index());
+            let weight = edge.weight.as_f64();
+            if from == to {
+                loops[from] += 2.0 * weight;
+            } else {
+                links[from].push((to, weight));
+                links[to].push((from, weight));
+            }
+        }
+        Level::new(links, loops)
+    }
+
+    fn new(links: Vec<Vec<(usize, f64)>>, loops: Vec<f64>) -> Level {
+        let degrees: Vec<f64> = links
+            .iter()
+            .zip(&loops)
+            .map(|(list, own)| own + list.iter().map(|&(_, w)| w).sum::<f64>())
+            .collect();
+        let total = degrees.iter().sum();
+        Level {
+            links,
+            loops,
+            degrees,
+            total,
+        }
+    }
+
+    // greedy pass over the nodes until no single move helps
+    // returns whether anything moved and the community of every node, numbered from 0
+    fn move_nodes(&self) -> (bool, Vec<usize>) {
+        let n = self.links.len();
+        let mut community: Vec<usize> = (0..n).collect();
+        // total degree of every community
+        let mut totals = self.degrees.clone();
+        let mut weight_to: HashMap<usize, f64> = HashMap::new();
+        let mut moved = false;
+        if self.total == 0.0 {
+            return (false, community);
+        }
+        loop {
+            let mut improved = false;
+            for node in 0..n {
+                let own = community[node];
+                let degree = self.degrees[node];
+                weight_to.clear();
+                for &(next, weight) in &self.links[node] {
+                    *weight_to.entry(community[next]).or_insert(0.0) += weight;
+                }
+                totals[own] -= degree;
+                // modularity gained by putting the node into a community, up to a constant factor
+                let gain = |c: usize, totals: &[f64]| {
+                    weight_to.get(&c).copied().unwrap_or(0.0) - totals[c] * degree / self.total
+                };
+                // staying put wins ties, other ties go to the smallest community id
+                let mut best = own;
+                let mut best_gain = gain(own, &totals);
+                for &c in weight_to.keys() {
+                    let g = gain(c, &totals);
+                    let tied = (g - best_gain).abs() <= EPSILON;
+                    if g > best_gain + EPSILON || (tied && best != own && c < best) {
+                        best = c;
+                        best_gain = g;
+                    }
+                }
+                totals[best] += degree;
+                if best != own {
+                    community[node] = best;
+                    improved = true;
+                    moved = true;
+                }
+            }
+            if !improved {
+                break;
+            }
+        }
+        let renumbered = Components::from_raw_labels(&community);
+        (moved, renumbered.labels().to_vec())
+    }
+
+    // one node per community, links between communities add up
+    fn aggregate(&self, communities: &[usize]) -> Level {
+        let count = communities.iter().max().map_or(0, |&c| c + 1);
+        let mut loops = vec![0.0; count];
+        let mut merged: Vec<HashMap<usize, f64>> = vec![HashMap::new(); count];
+        for (node, list) in self.links.iter().enumerate() {
+            let from = communities[node];
+            loops[from] += self.loops[node];
+            for &(next, weight) in list {
+                let to = communities[next];
+                if to == from {
+                    loops[from] += weight;
+                } else {
+                    *merged[from].entry(to).or_insert(0.0) += weight;
+                }
+            }
+        }
+        let links = merged
+            .into_iter()
+            .map(|map| {
+                let mut list: Vec<(usize, f64)> = map.into_iter().collect();
+                // hash order would make the next pass depend on the run
+                list.sort_by_key(|&(c, _)| c);
+                list
+            })
+            .collect();
+        Level::new(links, loops)
+    }
+}
+
+// small xorshift generator so label propagation doesn't need a dependency, not for anything else
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> XorShift {
+        // the state must never be zero
+        XorShift((seed ^ 0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use super::union_find::UnionFind;
+use super::{EdgeType, Graph, NodeId};
+
+// every node labelled with the id of the component it sits in, connected or strongly connected
+// component ids run from 0 in the order their first node appears in the graph
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Components {
+    labels: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl Components {
+    // takes any labelling where equal labels mean the same component and
+    // renumbers it so ids start at 0 and follow node order
+    pub(super) fn from_raw_labels(raw: &[usize]) -> Components {
+        let mut raw_to_label = std::collections::HashMap::new();
+        let mut labels = Vec::with_capacity(raw.len());
+        let mut sizes = Vec::new();
+        for &r in raw {
+            let label = *raw_to_label.entry(r).or_insert_with(|| {
+                sizes.push(0);
+                sizes.len() - 1
+            });
+            labels.push(label);
+            sizes[label] += 1;
+        }
+        Components { labels, sizes }
+    }
+
+    pub fn count(&self) -> usize {
+        self.sizes.len()
+    }
+
+    pub fn component_of(&self, node: NodeId) -> usize {
+        self.labels[node.index()]
+    }
+
+    // component id of every node, indexed by node index
+    pub fn labels(&self) -> &[usize] {
+        &self.labels
+    }
+
+    pub fn size(&self, component: usize) -> usize {
+        self.sizes[component]
+    }
+
+    pub fn sizes(&self) -> &[usize] {
+        &self.sizes
+    }
+
+    pub fn same_component(&self, a: NodeId, b: NodeId) -> bool {
+        self.labels[a.index()] == self.labels[b.index()]
+    }
+
+    pub fn nodes_in(&self, component: usize) -> Vec<NodeId> {
+        self.labels
+            .iter()
+            .enumerate()
+            .filter(|(_, &label)| label == component)
+            .map(|(i, _)| NodeId::new(i))
+            .collect()
+    }
+}
+
+impl<N, E, Ty: EdgeType> Graph<N, E, Ty> {
+    // connected components, edge direction is ignored so directed graphs get their weak components
+    pub fn connected_components(&self) -> Components {
+        let mut sets = UnionFind::new(self.node_count());
+        for (_, edge) in self.edges() {
+            sets.union(edge.from.index(), edge.to.index());
+        }
+        let roots: Vec<usize> = (0..self.node_count()).map(|i| sets.find(i)).collect();
+        Components::from_raw_labels(&roots)
+    }
+
+    // true when every node can reach every other one, ignoring direction
+    pub fn is_connected(&self) -> bool {
+        self.connected_components().count() <= 1
+    }
+}
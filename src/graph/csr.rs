@@ -0,0 +1,197 @@
+use super::{Adjacency, Edge, EdgeId, EdgeRef, EdgeType, Graph, NodeId, Undirected};
+use std::marker::PhantomData;
+use std::ops::{Index, Range};
+
+// one compressed sparse row table, the edges of node i sit in slots offsets[i]..offsets[i + 1]
+// and every slot stores the node at the other end, the edge id and a copy of the weight
+#[derive(Clone, Debug)]
+struct Rows<E> {
+    offsets: Vec<usize>,
+    nodes: Vec<NodeId>,
+    edges: Vec<EdgeId>,
+    weights: Vec<E>,
+}
+
+impl<E: Clone> Rows<E> {
+    // other_end picks the neighbor of a node along an edge
+    fn build<F>(lists: &[Vec<EdgeId>], edges: &[Edge<E>], other_end: F) -> Rows<E>
+    where
+        F: Fn(NodeId, &Edge<E>) -> NodeId,
+    {
+        let slots = lists.iter().map(Vec::len).sum();
+        let mut rows = Rows {
+            offsets: Vec::with_capacity(lists.len() + 1),
+            nodes: Vec::with_capacity(slots),
+            edges: Vec::with_capacity(slots),
+            weights: Vec::with_capacity(slots),
+        };
+        rows.offsets.push(0);
+        for (i, list) in lists.iter().enumerate() {
+            for &e in list {
+                let edge = &edges[e.0];
+                rows.nodes.push(other_end(NodeId(i), edge));
+                rows.edges.push(e);
+                rows.weights.push(edge.weight.clone());
+            }
+            rows.offsets.push(rows.nodes.len());
+        }
+        rows
+    }
+}
+
+impl<E> Rows<E> {
+    fn row(&self, id: NodeId) -> Range<usize> {
+        self.offsets[id.0]..self.offsets[id.0 + 1]
+    }
+}
+
+// immutable snapshot of a Graph laid out for fast lookups, made by Graph::freeze
+// node and edge ids are the same as in the graph it came from
+#[derive(Clone, Debug)]
+pub struct Csr<N, E, Ty = Undirected> {
+    nodes: Vec<N>,
+    edge_count: usize,
+    outgoing: Rows<E>,
+    // only kept for directed graphs, undirected ones read outgoing both ways
+    incoming: Option<Rows<E>>,
+    ty: PhantomData<Ty>,
+}
+
+impl<N, E: Clone, Ty: EdgeType> Graph<N, E, Ty> {
+    // packs the adjacency lists into flat arrays, for graphs that are only queried once built
+    pub fn freeze(self) -> Csr<N, E, Ty> {
+        let outgoing = Rows::build(&self.outgoing, &self.edges, |id, edge| {
+            if edge.from == id {
+                edge.to
+            } else {
+                edge.from
+            }
+        });
+        let incoming = if Ty::is_directed() {
+            Some(Rows::build(&self.incoming, &self.edges, |_, edge| {
+                edge.from
+            }))
+        } else {
+            None
+        };
+        Csr {
+            nodes: self.nodes,
+            edge_count: self.edges.len(),
+            outgoing,
+            incoming,
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<N, E, Ty: EdgeType> Csr<N, E, Ty> {
+    pub fn is_directed(&self) -> bool {
+        Ty::is_directed()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    pub fn node(&self, id: NodeId) -> Option<&N> {
+        self.nodes.get(id.0)
+    }
+
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.nodes.len()).map(NodeId)
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &N)> {
+        self.nodes.iter().enumerate().map(|(i, n)| (NodeId(i), n))
+    }
+
+    pub fn check_if_node_exist(&self, id: NodeId) -> bool {
+        id.0 < self.nodes.len()
+    }
+
+    pub fn find_node<F: Fn(&N) -> bool>(&self, predicate: F) -> Option<NodeId> {
+        self.nodes.iter().position(predicate).map(NodeId)
+    }
+
+    // edges leaving a node, oriented so `from` is the given node
+    pub fn edges_from(&self, id: NodeId) -> impl Iterator<Item = EdgeRef<'_, E>> {
+        let rows = &self.outgoing;
+        rows.row(id).map(move |slot| EdgeRef {
+            id: rows.edges[slot],
+            from: id,
+            to: rows.nodes[slot],
+            weight: &rows.weights[slot],
+        })
+    }
+
+    // edges that can be walked into a node, oriented so `to` is the given node
+    pub fn edges_to(&self, id: NodeId) -> impl Iterator<Item = EdgeRef<'_, E>> {
+        let rows = self.incoming.as_ref().unwrap_or(&self.outgoing);
+        rows.row(id).map(move |slot| EdgeRef {
+            id: rows.edges[slot],
+            from: rows.nodes[slot],
+            to: id,
+            weight: &rows.weights[slot],
+        })
+    }
+
+    // nodes reachable over one edge
+    pub fn neighbors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.outgoing.nodes[self.outgoing.row(id)].iter().copied()
+    }
+
+    pub fn out_degree(&self, id: NodeId) -> usize {
+        self.outgoing.row(id).len()
+    }
+
+    pub fn in_degree(&self, id: NodeId) -> usize {
+        self.incoming
+            .as_ref()
+            .unwrap_or(&self.outgoing)
+            .row(id)
+            .len()
+    }
+
+    // Breath first tree traversal
+    pub fn bft(&self, start: NodeId) -> Option<Vec<NodeId>> {
+        if !self.check_if_node_exist(start) {
+            return None;
+        }
+        Some(self.bfs(start).collect())
+    }
+
+    // Depth first tree traversal
+    pub fn dft(&self, start: NodeId) -> Option<Vec<NodeId>> {
+        if !self.check_if_node_exist(start) {
+            return None;
+        }
+        Some(self.dfs(start).collect())
+    }
+}
+
+impl<N, E, Ty: EdgeType> Adjacency for Csr<N, E, Ty> {
+    type Weight = E;
+
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn edges_from(&self, id: NodeId) -> impl Iterator<Item = EdgeRef<'_, E>> {
+        Csr::edges_from(self, id)
+    }
+
+    fn edges_to(&self, id: NodeId) -> impl Iterator<Item = EdgeRef<'_, E>> {
+        Csr::edges_to(self, id)
+    }
+}
+
+impl<N, E, Ty> Index<NodeId> for Csr<N, E, Ty> {
+    type Output = N;
+    fn index(&self, id: NodeId) -> &N {
+        &self.nodes[id.0]
+    }
+}
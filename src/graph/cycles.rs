@@ -0,0 +1,121 @@
+use super::{Directed, EdgeId, Graph, NodeId, Undirected};
+
+// a closed walk, edges[i] joins nodes[i] to nodes[i + 1] and the last edge leads back to nodes[0]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub nodes: Vec<NodeId>,
+    pub edges: Vec<EdgeId>,
+}
+
+// reads a cycle off a dfs call stack, from the entry for `to` up to the top, closed by `back_edge`
+fn cycle_from_stack(
+    call: &[(usize, usize, Option<EdgeId>)],
+    to: usize,
+    back_edge: EdgeId,
+) -> Cycle {
+    let start = call
+        .iter()
+        .position(|&(node, _, _)| node == to)
+        .expect("back edge target is not on the stack");
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+    for (i, &(node, _, _)) in call[start..].iter().enumerate() {
+        nodes.push(NodeId::new(node));
+        if let Some(&(_, _, Some(edge))) = call.get(start + i + 1) {
+            edges.push(edge);
+        }
+    }
+    edges.push(back_edge);
+    Cycle { nodes, edges }
+}
+
+impl<N, E> Graph<N, E, Directed> {
+    // nodes ordered so every edge points forward, or the cycle that makes that impossible
+    pub fn topological_order(&self) -> Result<Vec<NodeId>, Cycle> {
+        const NEW: u8 = 0;
+        const ON_STACK: u8 = 1;
+        const DONE: u8 = 2;
+        let mut state = vec![NEW; self.node_count()];
+        let mut finished = Vec::with_capacity(self.node_count());
+        // node, position in its outgoing edges, edge used to get there
+        let mut call: Vec<(usize, usize, Option<EdgeId>)> = Vec::new();
+
+        for root in 0..self.node_count() {
+            if state[root] != NEW {
+                continue;
+            }
+            state[root] = ON_STACK;
+            call.push((root, 0, None));
+            while let Some((v, pos, _)) = call.last_mut() {
+                let v = *v;
+                if *pos < self.outgoing[v].len() {
+                    let edge = self.outgoing[v][*pos];
+                    *pos += 1;
+                    let w = self.edges[edge.0].to.0;
+                    match state[w] {
+                        NEW => {
+                            state[w] = ON_STACK;
+                            call.push((w, 0, Some(edge)));
+                        }
+                        ON_STACK => return Err(cycle_from_stack(&call, w, edge)),
+                        _ => {}
+                    }
+                } else {
+                    state[v] = DONE;
+                    finished.push(NodeId(v));
+                    call.pop();
+                }
+            }
+        }
+        finished.reverse();
+        Ok(finished)
+    }
+
+    pub fn find_cycle(&self) -> Option<Cycle> {
+        self.topological_order().err()
+    }
+}
+
+impl<N, E> Graph<N, E, Undirected> {
+    // any cycle, self loops included, None when the graph is a forest
+    pub fn find_cycle(&self) -> Option<Cycle> {
+        let mut visited = vec![false; self.node_count()];
+        let mut on_stack = vec![false; self.node_count()];
+        let mut call: Vec<(usize, usize, Option<EdgeId>)> = Vec::new();
+
+        for root in 0..self.node_count() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            on_stack[root] = true;
+            call.push((root, 0, None));
+            while let Some((v, pos, parent_edge)) = call.last_mut() {
+                let v = *v;
+                let parent_edge = *parent_edge;
+                if *pos < self.outgoing[v].len() {
+                    let edge = self.outgoing[v][*pos];
+                    *pos += 1;
+                    // don't walk straight back down the edge we came in on
+                    if Some(edge) == parent_edge {
+                        continue;
+                    }
+                    let e = &self.edges[edge.0];
+                    let w = if e.from.0 == v { e.to.0 } else { e.from.0 };
+                    if on_stack[w] {
+                        return Some(cycle_from_stack(&call, w, edge));
+                    }
+                    if !visited[w] {
+                        visited[w] = true;
+                        on_stack[w] = true;
+                        call.push((w, 0, Some(edge)));
+                    }
+                } else {
+                    on_stack[v] = false;
+                    call.pop();
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,110 @@
+use super::{Edge, EdgeId, EdgeType, Graph, NodeId, Position};
+use std::fmt::Display;
+use std::io::{self, Write};
+
+// attribute list written out as [name="value", ...]
+pub type DotAttributes = Vec<(&'static str, String)>;
+
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn write_attributes<W: Write>(
+    out: &mut W,
+    attributes: &[(&'static str, String)],
+) -> io::Result<()> {
+    if attributes.is_empty() {
+        return Ok(());
+    }
+    write!(out, " [")?;
+    for (i, (name, value)) in attributes.iter().enumerate() {
+        if i > 0 {
+            write!(out, ", ")?;
+        }
+        write!(out, "{}={}", name, quote(value))?;
+    }
+    write!(out, "]")
+}
+
+impl<N, E, Ty: EdgeType> Graph<N, E, Ty> {
+    // writes the graph in Graphviz DOT, nodes are named n<index> and the closures pick their attributes
+    // node_defaults go on the `node [...]` line, handy for shape and size
+    pub fn write_dot_with<W, NA, EA>(
+        &self,
+        out: &mut W,
+        node_defaults: &[(&'static str, String)],
+        node_attributes: NA,
+        edge_attributes: EA,
+    ) -> io::Result<()>
+    where
+        W: Write,
+        NA: Fn(NodeId, &N) -> DotAttributes,
+        EA: Fn(EdgeId, &Edge<E>) -> DotAttributes,
+    {
+        let (kind, arrow) = if Ty::is_directed() {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        writeln!(out, "{} {{", kind)?;
+        if !node_defaults.is_empty() {
+            write!(out, "    node")?;
+            write_attributes(out, node_defaults)?;
+            writeln!(out, ";")?;
+        }
+        for (id, node) in self.nodes() {
+            write!(out, "    n{}", id.index())?;
+            write_attributes(out, &node_attributes(id, node))?;
+            writeln!(out, ";")?;
+        }
+        for (id, edge) in self.edges() {
+            write!(
+                out,
+                "    n{} {} n{}",
+                edge.from.index(),
+                arrow,
+                edge.to.index()
+            )?;
+            write_attributes(out, &edge_attributes(id, edge))?;
+            writeln!(out, ";")?;
+        }
+        writeln!(out, "}}")
+    }
+}
+
+impl<N: Position, E: Display, Ty: EdgeType> Graph<N, E, Ty> {
+    // DOT with every node pinned at its position and every edge labelled with its weight
+    // y is flipped since Graphviz puts the origin bottom left, render with `neato -n`
+    pub fn write_dot<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        self.write_dot_with(
+            out,
+            &[],
+            |id, node| {
+                let (x, y) = node.position();
+                vec![
+                    ("label", id.index().to_string()),
+                    ("pos", format!("{},{}", x, 0.0 - y)),
+                ]
+            },
+            |_, edge| vec![("label", edge.weight.to_string())],
+        )
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut out = Vec::new();
+        self.write_dot(&mut out)
+            .expect("writing to a Vec can't fail");
+        String::from_utf8(out).expect("DOT output is always utf8")
+    }
+}
@@ -0,0 +1,145 @@
+use super::union_find::UnionFind;
+use super::{EdgeId, EdgeType, Graph, NodeId};
+
+// a walk using every edge exactly once, edges[i] joins nodes[i] to nodes[i + 1]
+// a circuit ends on the node it started from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trail {
+    pub nodes: Vec<NodeId>,
+    pub edges: Vec<EdgeId>,
+}
+
+impl Trail {
+    pub fn is_closed(&self) -> bool {
+        self.nodes.first() == self.nodes.last()
+    }
+}
+
+impl<N, E, Ty: EdgeType> Graph<N, E, Ty> {
+    // true when a closed walk can use every edge once, a graph without edges has none
+    pub fn has_eulerian_circuit(&self) -> bool {
+        matches!(self.euler_start(), Some((_, true)))
+    }
+
+    // true when some walk, closed or not, can use every edge once
+    pub fn has_eulerian_path(&self) -> bool {
+        self.euler_start().is_some()
+    }
+
+    // Hierholzer's algorithm, None if there is no Eulerian circuit
+    pub fn eulerian_circuit(&self) -> Option<Trail> {
+        match self.euler_start()? {
+            (start, true) => Some(self.hierholzer(start)),
+            (_, false) => None,
+        }
+    }
+
+    // Hierholzer's algorithm, a circuit when there is one
+    // otherwise the walk has to start at the node with an odd degree, or one more edge out than in
+    pub fn eulerian_path(&self) -> Option<Trail> {
+        let (start, _) = self.euler_start()?;
+        Some(self.hierholzer(start))
+    }
+
+    // where an Eulerian walk has to start and whether it comes back there, None if there is no walk
+    fn euler_start(&self) -> Option<(NodeId, bool)> {
+        if self.edge_count() == 0 {
+            return None;
+        }
+        // out minus in for directed graphs, the degree for undirected ones, self loops count twice
+        let mut balance = vec![0i64; self.node_count()];
+        let mut sets = UnionFind::new(self.node_count());
+        for (_, edge) in self.edges() {
+            let (from, to) = (edge.from.index(), edge.to.index());
+            if Ty::is_directed() {
+                balance[from] += 1;
+                balance[to] -= 1;
+            } else {
+                balance[from] += 1;
+                balance[to] += 1;
+            }
+            sets.union(from, to);
+        }
+        // every edge has to be reachable from every other one
+        let first = self.edges().next().unwrap().1.from.index();
+        let mut touched = self.edges().flat_map(|(_, e)| [e.from, e.to]);
+        if !touched.all(|node| sets.same_set(first, node.index())) {
+            return None;
+        }
+
+        let mut odd = Vec::new();
+        let mut closed = true;
+        let mut start = NodeId::new(first);
+        for (i, &b) in balance.iter().enumerate() {
+            if Ty::is_directed() {
+                match b {
+                    0 => {}
+                    1 if closed => {
+                        closed = false;
+                        start = NodeId::new(i);
+                        odd.push(i);
+                    }
+                    -1 => odd.push(i),
+                    _ => return None,
+                }
+            } else if b % 2 == 1 {
+                if odd.is_empty() {
+                    start = NodeId::new(i);
+                }
+                odd.push(i);
+            }
+        }
+        if Ty::is_directed() {
+            // one node with an extra edge out needs exactly one with an extra edge in
+            match odd.len() {
+                0 => Some((start, true)),
+                2 if !closed => Some((start, false)),
+                _ => None,
+            }
+        } else {
+            match odd.len() {
+                0 => Some((start, true)),
+                2 => Some((start, false)),
+                _ => None,
+            }
+        }
+    }
+
+    // walks unused edges until stuck, then backs up, splicing in the detours found along the way
+    fn hierholzer(&self, start: NodeId) -> Trail {
+        let mut used = vec![false; self.edge_count()];
+        // how far into its outgoing list every node has looked
+        let mut next_edge = vec![0; self.node_count()];
+        let mut stack: Vec<(NodeId, Option<EdgeId>)> = vec![(start, None)];
+        let mut walk = Vec::with_capacity(self.edge_count() + 1);
+
+        while let Some(&(node, via)) = stack.last() {
+            let list = &self.outgoing[node.0];
+            while next_edge[node.0] < list.len() && used[list[next_edge[node.0]].0] {
+                next_edge[node.0] += 1;
+            }
+            match list.get(next_edge[node.0]) {
+                Some(&id) => {
+                    used[id.0] = true;
+                    let edge = &self.edges[id.0];
+                    let to = if edge.from == node {
+                        edge.to
+                    } else {
+                        edge.from
+                    };
+                    stack.push((to, Some(id)));
+                }
+                None => {
+                    stack.pop();
+                    walk.push((node, via));
+                }
+            }
+        }
+
+        walk.reverse();
+        Trail {
+            nodes: walk.iter().map(|&(node, _)| node).collect(),
+            edges: walk.iter().filter_map(|&(_, via)| via).collect(),
+        }
+    }
+}
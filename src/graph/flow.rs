@@ -0,0 +1,198 @@
+use super::{EdgeId, EdgeType, Graph, NodeId, Weight};
+use std::collections::VecDeque;
+
+// how much flow goes through one edge and which way, undirected edges can carry it either way
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EdgeFlow<E> {
+    pub from: NodeId,
+    pub to: NodeId,
+    pub amount: E,
+}
+
+// a maximum flow and the minimum cut that limits it
+#[derive(Clone, Debug)]
+pub struct Flow<E> {
+    pub value: E,
+    // indexed by edge id
+    pub edge_flows: Vec<EdgeFlow<E>>,
+    // edges crossing from the source side of the cut to the sink side, all of them saturated
+    pub min_cut: Vec<EdgeId>,
+    source_side: Vec<bool>,
+}
+
+impl<E> Flow<E> {
+    pub fn flow(&self, edge: EdgeId) -> &EdgeFlow<E> {
+        &self.edge_flows[edge.index()]
+    }
+
+    // true for nodes still reachable from the source once the flow is pushed
+    pub fn is_source_side(&self, node: NodeId) -> bool {
+        self.source_side[node.index()]
+    }
+
+    pub fn source_side(&self) -> Vec<NodeId> {
+        self.nodes_on(true)
+    }
+
+    pub fn sink_side(&self) -> Vec<NodeId> {
+        self.nodes_on(false)
+    }
+
+    fn nodes_on(&self, source_side: bool) -> Vec<NodeId> {
+        (0..self.source_side.len())
+            .filter(|&i| self.source_side[i] == source_side)
+            .map(NodeId::new)
+            .collect()
+    }
+}
+
+// residual network, arc 2k runs along edge k and arc 2k + 1 runs back against it
+struct Residual<C> {
+    heads: Vec<usize>,
+    residual: Vec<C>,
+    arcs_out: Vec<Vec<usize>>,
+}
+
+impl<C: Weight> Residual<C> {
+    // edges of the residual network still able to carry flow out of a node
+    fn open_arcs(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.arcs_out[node]
+            .iter()
+            .copied()
+            .filter(move |&arc| self.residual[arc] > C::zero())
+    }
+
+    // nodes reachable from source over arcs with capacity left, with the arc used to reach them
+    fn bfs(&self, source: usize) -> Vec<Option<usize>> {
+        let mut reached_by = vec![None; self.arcs_out.len()];
+        let mut seen = vec![false; self.arcs_out.len()];
+        let mut queue = VecDeque::new();
+        seen[source] = true;
+        queue.push_back(source);
+        while let Some(node) = queue.pop_front() {
+            for arc in self.open_arcs(node) {
+                let next = self.heads[arc];
+                if !seen[next] {
+                    seen[next] = true;
+                    reached_by[next] = Some(arc);
+                    queue.push_back(next);
+                }
+            }
+        }
+        reached_by
+    }
+}
+
+impl<N, E, Ty: EdgeType> Graph<N, E, Ty> {
+    // Edmonds-Karp over any capacity the caller picks for each edge
+    fn max_flow_by<C, F>(&self, source: NodeId, sink: NodeId, capacity: F) -> Option<Flow<C>>
+    where
+        C: Weight,
+        F: Fn(EdgeId) -> C,
+    {
+        if !self.check_if_node_exist(source) || !self.check_if_node_exist(sink) || source == sink {
+            return None;
+        }
+        let mut network = Residual {
+            heads: Vec::with_capacity(self.edge_count() * 2),
+            residual: Vec::with_capacity(self.edge_count() * 2),
+            arcs_out: vec![Vec::new(); self.node_count()],
+        };
+        for (id, edge) in self.edges() {
+            let cap = capacity(id);
+            let back = if Ty::is_directed() { C::zero() } else { cap };
+            network.arcs_out[edge.from.index()].push(network.heads.len());
+            network.heads.push(edge.to.index());
+            network.residual.push(cap);
+            network.arcs_out[edge.to.index()].push(network.heads.len());
+            network.heads.push(edge.from.index());
+            network.residual.push(back);
+        }
+
+        // keep pushing flow down the shortest augmenting path until there isn't one
+        let mut value = C::zero();
+        loop {
+            let reached_by = network.bfs(source.index());
+            if reached_by[sink.index()].is_none() {
+                break;
+            }
+            let mut bottleneck = None;
+            let mut node = sink.index();
+            while let Some(arc) = reached_by[node] {
+                let left = network.residual[arc];
+                bottleneck = Some(match bottleneck {
+                    Some(b) if b < left => b,
+                    _ => left,
+                });
+                node = network.heads[arc ^ 1];
+            }
+            let bottleneck = bottleneck.expect("augmenting path has no arcs");
+            let mut node = sink.index();
+            while let Some(arc) = reached_by[node] {
+                network.residual[arc] = network.residual[arc] - bottleneck;
+                network.residual[arc ^ 1] = network.residual[arc ^ 1] + bottleneck;
+                node = network.heads[arc ^ 1];
+            }
+            value = value + bottleneck;
+        }
+
+        let edge_flows = self
+            .edges()
+            .map(|(id, edge)| {
+                let cap = capacity(id);
+                let left = network.residual[id.index() * 2];
+                if left <= cap {
+                    EdgeFlow {
+                        from: edge.from,
+                        to: edge.to,
+                        amount: cap - left,
+                    }
+                } else {
+                    EdgeFlow {
+                        from: edge.to,
+                        to: edge.from,
+                        amount: left - cap,
+                    }
+                }
+            })
+            .collect();
+
+        let reached_by = network.bfs(source.index());
+        let source_side: Vec<bool> = (0..self.node_count())
+            .map(|i| i == source.index() || reached_by[i].is_some())
+            .collect();
+        let min_cut = self
+            .edges()
+            .filter(|(_, edge)| {
+                let (from, to) = (source_side[edge.from.index()], source_side[edge.to.index()]);
+                if Ty::is_directed() {
+                    from && !to
+                } else {
+                    from != to
+                }
+            })
+            .map(|(id, _)| id)
+            .collect();
+
+        Some(Flow {
+            value,
+            edge_flows,
+            min_cut,
+            source_side,
+        })
+    }
+
+    // number of routes from source to sink that never share an edge
+    pub fn edge_disjoint_paths(&self, source: NodeId, sink: NodeId) -> usize {
+        self.max_flow_by(source, sink, |_| 1usize)
+            .map_or(0, |flow| flow.value)
+    }
+}
+
+impl<N, E: Weight, Ty: EdgeType> Graph<N, E, Ty> {
+    // maximum flow from source to sink using the edge weights as capacities
+    // None if either node is missing or they are the same node
+    pub fn max_flow(&self, source: NodeId, sink: NodeId) -> Option<Flow<E>> {
+        self.max_flow_by(source, sink, |id| self[id].weight)
+    }
+}
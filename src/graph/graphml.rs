@@ -0,0 +1,537 @@
+use super::{EdgeType, Graph, Node, NodeId, ParseError};
+use std::collections::HashMap;
+use std::convert::{TryFrom, TryInto};
+use std::io::{self, Write};
+
+// the value types GraphML attributes can be declared with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttrType {
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    String,
+}
+
+impl AttrType {
+    fn name(self) -> &'static str {
+        match self {
+            AttrType::Boolean => "boolean",
+            AttrType::Int => "int",
+            AttrType::Long => "long",
+            AttrType::Float => "float",
+            AttrType::Double => "double",
+            AttrType::String => "string",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<AttrType> {
+        Some(match name {
+            "boolean" => AttrType::Boolean,
+            "int" => AttrType::Int,
+            "long" => AttrType::Long,
+            "float" => AttrType::Float,
+            "double" => AttrType::Double,
+            "string" => AttrType::String,
+            _ => return None,
+        })
+    }
+
+    fn parse(self, text: &str) -> Option<AttrValue> {
+        let text = text.trim();
+        Some(match self {
+            AttrType::Boolean => AttrValue::Boolean(text.parse().ok()?),
+            AttrType::Int => AttrValue::Int(text.parse().ok()?),
+            AttrType::Long => AttrValue::Long(text.parse().ok()?),
+            AttrType::Float => AttrValue::Float(text.parse().ok()?),
+            AttrType::Double => AttrValue::Double(text.parse().ok()?),
+            AttrType::String => AttrValue::String(text.to_string()),
+        })
+    }
+}
+
+// a typed attribute value
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttrValue {
+    Boolean(bool),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+}
+
+impl AttrValue {
+    pub fn attr_type(&self) -> AttrType {
+        match self {
+            AttrValue::Boolean(_) => AttrType::Boolean,
+            AttrValue::Int(_) => AttrType::Int,
+            AttrValue::Long(_) => AttrType::Long,
+            AttrValue::Float(_) => AttrType::Float,
+            AttrValue::Double(_) => AttrType::Double,
+            AttrValue::String(_) => AttrType::String,
+        }
+    }
+
+    // whole numbers of either width
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            AttrValue::Int(v) => Some(v as i64),
+            AttrValue::Long(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            AttrValue::Int(v) => Some(v as f64),
+            AttrValue::Long(v) => Some(v as f64),
+            AttrValue::Float(v) => Some(v as f64),
+            AttrValue::Double(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AttrValue::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn text(&self) -> String {
+        match self {
+            AttrValue::Boolean(v) => v.to_string(),
+            AttrValue::Int(v) => v.to_string(),
+            AttrValue::Long(v) => v.to_string(),
+            AttrValue::Float(v) => v.to_string(),
+            AttrValue::Double(v) => v.to_string(),
+            AttrValue::String(v) => v.clone(),
+        }
+    }
+}
+
+// attributes by name
+pub type Attributes = HashMap<String, AttrValue>;
+
+// node and edge payloads that can be stored as GraphML attributes
+pub trait GraphMlData: Sized {
+    // names and types of the attributes this type reads and writes
+    fn graphml_keys() -> Vec<(&'static str, AttrType)>;
+    // fails when a value doesn't fit the type its key is declared with
+    fn to_graphml(&self) -> Result<Vec<(&'static str, AttrValue)>, String>;
+    // takes the attributes it understands out of the map, whatever is left is kept as extras
+    fn from_graphml(attributes: &mut Attributes) -> Result<Self, String>;
+}
+
+fn take_integer<T: TryFrom<i64>>(attributes: &mut Attributes, name: &str) -> Result<T, String> {
+    let value = attributes
+        .remove(name)
+        .ok_or_else(|| format!("missing attribute {}", name))?;
+    value
+        .as_i64()
+        .and_then(|v| T::try_from(v).ok())
+        .ok_or_else(|| format!("attribute {} is out of range or not a whole number", name))
+}
+
+// u64 and usize values past i64::MAX have no GraphML type to go in
+fn to_long<T: Copy + std::fmt::Display + TryInto<i64>>(
+    value: T,
+    name: &str,
+) -> Result<i64, String> {
+    value
+        .try_into()
+        .map_err(|_| format!("attribute {} is out of range: {}", name, value))
+}
+
+impl GraphMlData for Node {
+    fn graphml_keys() -> Vec<(&'static str, AttrType)> {
+        vec![
+            ("x", AttrType::Long),
+            ("y", AttrType::Long),
+            ("vec_coord", AttrType::Long),
+        ]
+    }
+
+    fn to_graphml(&self) -> Result<Vec<(&'static str, AttrValue)>, String> {
+        Ok(vec![
+            ("x", AttrValue::Long(i64::from(self.x))),
+            ("y", AttrValue::Long(i64::from(self.y))),
+            (
+                "vec_coord",
+                AttrValue::Long(to_long(self.vec_coord, "vec_coord")?),
+            ),
+        ])
+    }
+
+    fn from_graphml(attributes: &mut Attributes) -> Result<Self, String> {
+        Ok(Node {
+            x: take_integer(attributes, "x")?,
+            y: take_integer(attributes, "y")?,
+            vec_coord: take_integer(attributes, "vec_coord")?,
+        })
+    }
+}
+
+macro_rules! impl_graphml_weight {
+    ($($t:ty),*) => {
+        $(impl GraphMlData for $t {
+            fn graphml_keys() -> Vec<(&'static str, AttrType)> {
+                vec![("weight", AttrType::Long)]
+            }
+
+            fn to_graphml(&self) -> Result<Vec<(&'static str, AttrValue)>, String> {
+                Ok(vec![("weight", AttrValue::Long(to_long(*self, "weight")?))])
+            }
+
+            fn from_graphml(attributes: &mut Attributes) -> Result<Self, String> {
+                take_integer(attributes, "weight")
+            }
+        })*
+    };
+}
+
+impl_graphml_weight!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl GraphMlData for String {
+    fn graphml_keys() -> Vec<(&'static str, AttrType)> {
+        vec![("label", AttrType::String)]
+    }
+
+    fn to_graphml(&self) -> Result<Vec<(&'static str, AttrValue)>, String> {
+        Ok(vec![("label", AttrValue::String(self.clone()))])
+    }
+
+    fn from_graphml(attributes: &mut Attributes) -> Result<Self, String> {
+        match attributes.remove("label") {
+            Some(AttrValue::String(label)) => Ok(label),
+            Some(_) => Err(String::from("attribute label is not a string")),
+            None => Err(String::from("missing attribute label")),
+        }
+    }
+}
+
+impl GraphMlData for () {
+    fn graphml_keys() -> Vec<(&'static str, AttrType)> {
+        Vec::new()
+    }
+
+    fn to_graphml(&self) -> Result<Vec<(&'static str, AttrValue)>, String> {
+        Ok(Vec::new())
+    }
+
+    fn from_graphml(_: &mut Attributes) -> Result<Self, String> {
+        Ok(())
+    }
+}
+
+// a graph read from GraphML together with everything its payload types didn't claim
+#[derive(Clone, Debug)]
+pub struct GraphMl<N, E, Ty> {
+    pub graph: Graph<N, E, Ty>,
+    // id each node had in the file, indexed by node id
+    pub node_names: Vec<String>,
+    // leftover attributes, indexed by node and edge id
+    pub node_extras: Vec<Attributes>,
+    pub edge_extras: Vec<Attributes>,
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// collects the declared keys of one domain, payload keys first then extras in name order
+fn declare_keys(
+    payload: Vec<(&'static str, AttrType)>,
+    extras: &[Attributes],
+) -> Vec<(String, AttrType)> {
+    let mut keys: Vec<(String, AttrType)> = payload
+        .into_iter()
+        .map(|(name, kind)| (name.to_string(), kind))
+        .collect();
+    let mut extra_keys: Vec<(String, AttrType)> = Vec::new();
+    for attributes in extras {
+        for (name, value) in attributes {
+            let known = keys.iter().chain(extra_keys.iter()).any(|(n, _)| n == name);
+            if !known {
+                extra_keys.push((name.clone(), value.attr_type()));
+            }
+        }
+    }
+    extra_keys.sort_by(|a, b| a.0.cmp(&b.0));
+    keys.extend(extra_keys);
+    keys
+}
+
+fn write_data<W: Write>(
+    out: &mut W,
+    domain: &str,
+    payload: Result<Vec<(&'static str, AttrValue)>, String>,
+    extras: Option<&Attributes>,
+) -> io::Result<()> {
+    let payload = payload.map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message))?;
+    let mut data: Vec<(String, AttrValue)> = payload
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+    for (name, value) in extras.into_iter().flatten() {
+        if !data.iter().any(|(n, _)| n == name) {
+            data.push((name.clone(), value.clone()));
+        }
+    }
+    data.sort_by(|a, b| a.0.cmp(&b.0));
+    for (name, value) in data {
+        writeln!(
+            out,
+            "      <data key=\"{}_{}\">{}</data>",
+            domain,
+            escape(&name),
+            escape(&value.text())
+        )?;
+    }
+    Ok(())
+}
+
+// writes graph as GraphML, node_names and the extras can be shorter than the graph or empty
+fn write_document<N, E, Ty, W>(
+    out: &mut W,
+    graph: &Graph<N, E, Ty>,
+    node_names: &[String],
+    node_extras: &[Attributes],
+    edge_extras: &[Attributes],
+) -> io::Result<()>
+where
+    N: GraphMlData,
+    E: GraphMlData,
+    Ty: EdgeType,
+    W: Write,
+{
+    let names: Vec<String> = (0..graph.node_count())
+        .map(|i| match node_names.get(i) {
+            Some(name) => name.clone(),
+            None => format!("n{}", i),
+        })
+        .collect();
+    let node_keys = declare_keys(N::graphml_keys(), node_extras);
+    let edge_keys = declare_keys(E::graphml_keys(), edge_extras);
+
+    writeln!(out, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(
+        out,
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">"
+    )?;
+    for (domain, keys) in [("node", &node_keys), ("edge", &edge_keys)] {
+        for (name, kind) in keys.iter() {
+            writeln!(
+                out,
+                "  <key id=\"{0}_{1}\" for=\"{0}\" attr.name=\"{1}\" attr.type=\"{2}\"/>",
+                domain,
+                escape(name),
+                kind.name()
+            )?;
+        }
+    }
+    let direction = if Ty::is_directed() {
+        "directed"
+    } else {
+        "undirected"
+    };
+    writeln!(out, "  <graph edgedefault=\"{}\">", direction)?;
+    for (id, node) in graph.nodes() {
+        writeln!(out, "    <node id=\"{}\">", escape(&names[id.index()]))?;
+        write_data(out, "node", node.to_graphml(), node_extras.get(id.index()))?;
+        writeln!(out, "    </node>")?;
+    }
+    for (id, edge) in graph.edges() {
+        writeln!(
+            out,
+            "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">",
+            id.index(),
+            escape(&names[edge.from.index()]),
+            escape(&names[edge.to.index()])
+        )?;
+        write_data(
+            out,
+            "edge",
+            edge.weight.to_graphml(),
+            edge_extras.get(id.index()),
+        )?;
+        writeln!(out, "    </edge>")?;
+    }
+    writeln!(out, "  </graph>")?;
+    writeln!(out, "</graphml>")
+}
+
+// only fails when a payload value can't be written, writing to a Vec itself can't
+fn to_string<F: FnOnce(&mut Vec<u8>) -> io::Result<()>>(write: F) -> io::Result<String> {
+    let mut out = Vec::new();
+    write(&mut out)?;
+    Ok(String::from_utf8(out).expect("GraphML output is always utf8"))
+}
+
+impl<N: GraphMlData, E: GraphMlData, Ty: EdgeType> GraphMl<N, E, Ty> {
+    pub fn write_graphml<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        write_document(
+            out,
+            &self.graph,
+            &self.node_names,
+            &self.node_extras,
+            &self.edge_extras,
+        )
+    }
+
+    pub fn to_graphml(&self) -> io::Result<String> {
+        to_string(|out| self.write_graphml(out))
+    }
+
+    // reads the first graph in a GraphML document
+    // edges are added the way Ty stores them, whatever edgedefault the file declares
+    pub fn from_graphml(text: &str) -> Result<GraphMl<N, E, Ty>, ParseError> {
+        let document = roxmltree::Document::parse(text)
+            .map_err(|e| ParseError::new(e.pos().row as usize, e.to_string()))?;
+        let line_of = |node: roxmltree::Node| document.text_pos_at(node.range().start).row as usize;
+
+        // key id -> (attribute name, type, default)
+        let mut keys: HashMap<&str, (String, AttrType, Option<AttrValue>)> = HashMap::new();
+        let mut defaults: Vec<(&str, String, AttrValue)> = Vec::new();
+        for key in document
+            .root_element()
+            .children()
+            .filter(|n| n.has_tag_name("key"))
+        {
+            let line = line_of(key);
+            let id = key
+                .attribute("id")
+                .ok_or_else(|| ParseError::new(line, "key without an id"))?;
+            let name = key.attribute("attr.name").unwrap_or(id).to_string();
+            let kind = match key.attribute("attr.type") {
+                None => AttrType::String,
+                Some(kind) => AttrType::from_name(kind).ok_or_else(|| {
+                    ParseError::new(line, format!("unknown attribute type {}", kind))
+                })?,
+            };
+            let default = match key.children().find(|n| n.has_tag_name("default")) {
+                None => None,
+                Some(default) => {
+                    let value = kind.parse(default.text().unwrap_or("")).ok_or_else(|| {
+                        ParseError::new(line_of(default), format!("bad default for key {}", id))
+                    })?;
+                    Some(value)
+                }
+            };
+            if let Some(value) = &default {
+                let domain = key.attribute("for").unwrap_or("all");
+                defaults.push((domain, name.clone(), value.clone()));
+            }
+            keys.insert(id, (name, kind, default));
+        }
+
+        let graph_element = document
+            .root_element()
+            .children()
+            .find(|n| n.has_tag_name("graph"))
+            .ok_or_else(|| ParseError::new(1, "no graph element"))?;
+
+        let read_data =
+            |element: roxmltree::Node, domain: &str| -> Result<Attributes, ParseError> {
+                let mut attributes = Attributes::new();
+                for (key_domain, name, value) in defaults.iter() {
+                    if *key_domain == domain || *key_domain == "all" {
+                        attributes.insert(name.clone(), value.clone());
+                    }
+                }
+                for data in element.children().filter(|n| n.has_tag_name("data")) {
+                    let line = line_of(data);
+                    let key = data
+                        .attribute("key")
+                        .ok_or_else(|| ParseError::new(line, "data without a key"))?;
+                    let (name, kind, _) = keys
+                        .get(key)
+                        .ok_or_else(|| ParseError::new(line, format!("undeclared key {}", key)))?;
+                    let text = data.text().unwrap_or("");
+                    let value = kind.parse(text).ok_or_else(|| {
+                        ParseError::new(line, format!("{:?} is not a valid {}", text, kind.name()))
+                    })?;
+                    attributes.insert(name.clone(), value);
+                }
+                Ok(attributes)
+            };
+
+        let mut result = GraphMl {
+            graph: Graph::default(),
+            node_names: Vec::new(),
+            node_extras: Vec::new(),
+            edge_extras: Vec::new(),
+        };
+        let mut ids: HashMap<&str, NodeId> = HashMap::new();
+        for element in graph_element.children().filter(|n| n.has_tag_name("node")) {
+            let line = line_of(element);
+            let name = element
+                .attribute("id")
+                .ok_or_else(|| ParseError::new(line, "node without an id"))?;
+            if ids.contains_key(name) {
+                return Err(ParseError::new(
+                    line,
+                    format!("node {} declared twice", name),
+                ));
+            }
+            let mut attributes = read_data(element, "node")?;
+            let payload = N::from_graphml(&mut attributes)
+                .map_err(|message| ParseError::new(line, format!("node {}: {}", name, message)))?;
+            ids.insert(name, result.graph.add_node(payload));
+            result.node_names.push(name.to_string());
+            result.node_extras.push(attributes);
+        }
+        for element in graph_element.children().filter(|n| n.has_tag_name("edge")) {
+            let line = line_of(element);
+            let endpoint = |which: &str| -> Result<NodeId, ParseError> {
+                let name = element
+                    .attribute(which)
+                    .ok_or_else(|| ParseError::new(line, format!("edge without a {}", which)))?;
+                ids.get(name)
+                    .copied()
+                    .ok_or_else(|| ParseError::new(line, format!("unknown node {}", name)))
+            };
+            let (from, to) = (endpoint("source")?, endpoint("target")?);
+            let mut attributes = read_data(element, "edge")?;
+            let weight = E::from_graphml(&mut attributes)
+                .map_err(|message| ParseError::new(line, format!("edge: {}", message)))?;
+            let id = result.graph.add_edge(from, to, weight);
+            // a repeated edge only overwrites the weight, so its extras do the same
+            if id.index() == result.edge_extras.len() {
+                result.edge_extras.push(attributes);
+            } else {
+                result.edge_extras[id.index()] = attributes;
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl<N: GraphMlData, E: GraphMlData, Ty: EdgeType> Graph<N, E, Ty> {
+    // GraphML with the payload attributes only, nodes are named n<index>
+    pub fn write_graphml<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        write_document(out, self, &[], &[], &[])
+    }
+
+    pub fn to_graphml(&self) -> io::Result<String> {
+        to_string(|out| self.write_graphml(out))
+    }
+
+    // drops any attributes the payload types don't use, see GraphMl::from_graphml to keep them
+    pub fn from_graphml(text: &str) -> Result<Self, ParseError> {
+        GraphMl::from_graphml(text).map(|document| document.graph)
+    }
+}
@@ -0,0 +1,129 @@
+use super::{EdgeType, Graph, NodeId, Weight};
+use std::collections::VecDeque;
+
+// how far every node is from the node furthest away from it, None when some node can't be reached
+// D is a hop count or a summed weight depending on which method made it
+#[derive(Clone, Debug)]
+pub struct Eccentricities<D> {
+    values: Vec<Option<D>>,
+}
+
+impl<D: Copy + Ord> Eccentricities<D> {
+    pub fn of(&self, node: NodeId) -> Option<D> {
+        self.values.get(node.index()).copied().flatten()
+    }
+
+    pub fn values(&self) -> &[Option<D>] {
+        &self.values
+    }
+
+    // largest eccentricity, None if the graph is empty or some pair of nodes isn't connected
+    pub fn diameter(&self) -> Option<D> {
+        if self.values.is_empty() {
+            return None;
+        }
+        let mut diameter = None;
+        for &value in &self.values {
+            diameter = diameter.max(Some(value?));
+        }
+        diameter
+    }
+
+    // smallest eccentricity, nodes that can't reach everything are left out
+    pub fn radius(&self) -> Option<D> {
+        self.values.iter().flatten().min().copied()
+    }
+
+    // nodes whose eccentricity is the radius
+    pub fn center(&self) -> Vec<NodeId> {
+        self.nodes_at(self.radius())
+    }
+
+    // nodes whose eccentricity is the diameter, empty when there is no diameter
+    pub fn periphery(&self) -> Vec<NodeId> {
+        self.nodes_at(self.diameter())
+    }
+
+    fn nodes_at(&self, value: Option<D>) -> Vec<NodeId> {
+        if value.is_none() {
+            return Vec::new();
+        }
+        (0..self.values.len())
+            .filter(|&i| self.values[i] == value)
+            .map(NodeId)
+            .collect()
+    }
+}
+
+impl<N, E, Ty: EdgeType> Graph<N, E, Ty> {
+    // entry d is how many nodes have degree d, directed graphs count edges going both in and out
+    // a self loop counts twice either way, so the degrees add up to twice the edge count
+    pub fn degree_histogram(&self) -> Vec<usize> {
+        let mut histogram = Vec::new();
+        for id in self.node_ids() {
+            let degree = if Ty::is_directed() {
+                self.out_degree(id) + self.in_degree(id)
+            } else {
+                // an undirected loop is only listed once in the node's adjacency
+                let loops = self.edges_from(id).filter(|edge| edge.to == id).count();
+                self.out_degree(id) + loops
+            };
+            if histogram.len() <= degree {
+                histogram.resize(degree + 1, 0);
+            }
+            histogram[degree] += 1;
+        }
+        histogram
+    }
+
+    // eccentricity counted in edges, one breadth first search per node
+    pub fn eccentricities(&self) -> Eccentricities<usize> {
+        let mut depths = vec![None; self.node_count()];
+        let mut queue = VecDeque::new();
+        let values = self
+            .node_ids()
+            .map(|source| {
+                depths.iter_mut().for_each(|depth| *depth = None);
+                depths[source.index()] = Some(0);
+                queue.push_back(source);
+                let mut furthest = 0;
+                let mut reached = 1;
+                while let Some(node) = queue.pop_front() {
+                    let depth = depths[node.index()].unwrap();
+                    furthest = depth;
+                    for next in self.neighbors(node) {
+                        if depths[next.index()].is_none() {
+                            depths[next.index()] = Some(depth + 1);
+                            reached += 1;
+                            queue.push_back(next);
+                        }
+                    }
+                }
+                if reached == self.node_count() {
+                    Some(furthest)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        Eccentricities { values }
+    }
+}
+
+impl<N, E: Weight, Ty: EdgeType> Graph<N, E, Ty> {
+    // eccentricity counted in edge weight, one dijkstra per node
+    pub fn weighted_eccentricities(&self) -> Eccentricities<E> {
+        let values = self
+            .node_ids()
+            .map(|source| {
+                let paths = self.dijkstra_all(source).unwrap();
+                let mut furthest = E::zero();
+                for node in self.node_ids() {
+                    furthest = furthest.max(paths.distance(node)?);
+                }
+                Some(furthest)
+            })
+            .collect();
+        Eccentricities { values }
+    }
+}
@@ -0,0 +1,66 @@
+use super::union_find::UnionFind;
+use super::{EdgeId, Graph, Undirected, Weight};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// edges picked for a minimum spanning tree, or a forest when the graph is disconnected
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SpanningTree<E> {
+    pub edges: Vec<EdgeId>,
+    pub weight: E,
+}
+
+impl<N, E: Weight> Graph<N, E, Undirected> {
+    // Kruskal, sorts every edge and joins components with a union find
+    pub fn kruskal(&self) -> SpanningTree<E> {
+        let mut sorted: Vec<EdgeId> = self.edge_ids().collect();
+        sorted.sort_by_key(|&id| self[id].weight);
+
+        let mut sets = UnionFind::new(self.node_count());
+        let mut tree = SpanningTree {
+            edges: Vec::new(),
+            weight: E::zero(),
+        };
+        for id in sorted {
+            let edge = &self[id];
+            if sets.union(edge.from.index(), edge.to.index()) {
+                tree.edges.push(id);
+                tree.weight = tree.weight + edge.weight;
+            }
+        }
+        tree
+    }
+
+    // Prim, grows a tree out of every node not reached yet
+    pub fn prim(&self) -> SpanningTree<E> {
+        let mut in_tree = vec![false; self.node_count()];
+        let mut heap = BinaryHeap::new();
+        let mut tree = SpanningTree {
+            edges: Vec::new(),
+            weight: E::zero(),
+        };
+        for root in self.node_ids() {
+            if in_tree[root.index()] {
+                continue;
+            }
+            in_tree[root.index()] = true;
+            for edge in self.edges_from(root) {
+                heap.push(Reverse((*edge.weight, edge.id, edge.to)));
+            }
+            while let Some(Reverse((weight, id, node))) = heap.pop() {
+                if in_tree[node.index()] {
+                    continue;
+                }
+                in_tree[node.index()] = true;
+                tree.edges.push(id);
+                tree.weight = tree.weight + weight;
+                for edge in self.edges_from(node) {
+                    if !in_tree[edge.to.index()] {
+                        heap.push(Reverse((*edge.weight, edge.id, edge.to)));
+                    }
+                }
+            }
+        }
+        tree
+    }
+}
@@ -0,0 +1,108 @@
+use super::{Components, DiGraph, Directed, EdgeId, Graph, NodeId};
+use std::collections::HashMap;
+
+impl<N, E> Graph<N, E, Directed> {
+    // strongly connected components with Tarjan's algorithm
+    // iterative so long one way corridors don't overflow the stack
+    pub fn strongly_connected_components(&self) -> Components {
+        let n = self.node_count();
+        let mut index = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut raw = vec![0; n];
+        let mut next_index = 0;
+        let mut found = 0;
+        // node and how far through its outgoing edges we are
+        let mut call: Vec<(usize, usize)> = Vec::new();
+
+        for root in 0..n {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            index[root] = next_index;
+            low[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+            call.push((root, 0));
+
+            while let Some((v, pos)) = call.last_mut() {
+                let v = *v;
+                if *pos < self.outgoing[v].len() {
+                    let w = self.edges[self.outgoing[v][*pos].0].to.0;
+                    *pos += 1;
+                    if index[w] == usize::MAX {
+                        index[w] = next_index;
+                        low[w] = next_index;
+                        next_index += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        call.push((w, 0));
+                    } else if on_stack[w] {
+                        low[v] = low[v].min(index[w]);
+                    }
+                    continue;
+                }
+
+                call.pop();
+                if let Some(&(parent, _)) = call.last() {
+                    low[parent] = low[parent].min(low[v]);
+                }
+                if low[v] == index[v] {
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        raw[w] = found;
+                        if w == v {
+                            break;
+                        }
+                    }
+                    found += 1;
+                }
+            }
+        }
+        Components::from_raw_labels(&raw)
+    }
+
+    pub fn is_strongly_connected(&self) -> bool {
+        self.strongly_connected_components().count() <= 1
+    }
+
+    // collapses every strongly connected component into one node, the result is always acyclic
+    // node i holds the members of component i from strongly_connected_components
+    // and each edge holds the original edges running between its two components
+    // components with no outgoing edges are the regions you can't leave once inside
+    pub fn condensation(&self) -> DiGraph<Vec<NodeId>, Vec<EdgeId>> {
+        let components = self.strongly_connected_components();
+        let mut dag: DiGraph<Vec<NodeId>, Vec<EdgeId>> = Graph::new_directed();
+        // one pass over the labels, nodes_in per component would rescan every node each time
+        let mut members: Vec<Vec<NodeId>> = components
+            .sizes()
+            .iter()
+            .map(|&size| Vec::with_capacity(size))
+            .collect();
+        for (i, &component) in components.labels().iter().enumerate() {
+            members[component].push(NodeId::new(i));
+        }
+        for nodes in members {
+            dag.add_node(nodes);
+        }
+
+        let mut between: HashMap<(usize, usize), EdgeId> = HashMap::new();
+        for (id, edge) in self.edges() {
+            let from = components.component_of(edge.from);
+            let to = components.component_of(edge.to);
+            if from == to {
+                continue;
+            }
+            match between.get(&(from, to)) {
+                Some(&dag_edge) => dag.edges[dag_edge.0].weight.push(id),
+                None => {
+                    let dag_edge = dag.add_edge(NodeId::new(from), NodeId::new(to), vec![id]);
+                    between.insert((from, to), dag_edge);
+                }
+            }
+        }
+        dag
+    }
+}
@@ -0,0 +1,81 @@
+use super::{Edge, EdgeId, EdgeType, Graph, NodeId};
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// what a graph looks like on disk, edges point at nodes by index and the adjacency lists are rebuilt on load
+#[derive(Serialize)]
+struct GraphOut<'a, N, E> {
+    directed: bool,
+    nodes: &'a [N],
+    edges: &'a [Edge<E>],
+}
+
+#[derive(Deserialize)]
+struct GraphIn<N, E> {
+    directed: bool,
+    nodes: Vec<N>,
+    edges: Vec<Edge<E>>,
+}
+
+impl<N: Serialize, E: Serialize, Ty: EdgeType> Serialize for Graph<N, E, Ty> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        GraphOut {
+            directed: Ty::is_directed(),
+            nodes: &self.nodes,
+            edges: &self.edges,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, N, E, Ty> Deserialize<'de> for Graph<N, E, Ty>
+where
+    N: Deserialize<'de>,
+    E: Deserialize<'de>,
+    Ty: EdgeType,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let stored = GraphIn::<N, E>::deserialize(deserializer)?;
+        if stored.directed != Ty::is_directed() {
+            return Err(D::Error::custom(if stored.directed {
+                "expected an undirected graph but found a directed one"
+            } else {
+                "expected a directed graph but found an undirected one"
+            }));
+        }
+
+        let mut graph = Graph::with_capacity(stored.nodes.len(), stored.edges.len());
+        for node in stored.nodes {
+            graph.add_node(node);
+        }
+        // edges keep their ids, so they are linked up by hand instead of through add_edge
+        for (i, edge) in stored.edges.into_iter().enumerate() {
+            let (from, to) = (edge.from, edge.to);
+            if !graph.check_if_node_exist(from) || !graph.check_if_node_exist(to) {
+                return Err(D::Error::custom(format!(
+                    "edge {} points at a node that doesn't exist",
+                    i
+                )));
+            }
+            // one edge per pair, same as add_edge keeps it
+            if graph.find_edge(from, to).is_some() {
+                return Err(D::Error::custom(format!(
+                    "edge {} joins a pair of nodes an earlier edge already joins",
+                    i
+                )));
+            }
+            graph.edges.push(edge);
+            link(&mut graph, EdgeId(i), from, to);
+        }
+        Ok(graph)
+    }
+}
+
+fn link<N, E, Ty: EdgeType>(graph: &mut Graph<N, E, Ty>, id: EdgeId, from: NodeId, to: NodeId) {
+    graph.outgoing[from.0].push(id);
+    if Ty::is_directed() {
+        graph.incoming[to.0].push(id);
+    } else if from != to {
+        graph.outgoing[to.0].push(id);
+    }
+}
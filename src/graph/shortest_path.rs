@@ -0,0 +1,298 @@
+use super::{Adjacency, Csr, EdgeType, Graph, Node, NodeId, Weight};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// a route through the graph and the summed weight of its edges
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Path<E> {
+    pub nodes: Vec<NodeId>,
+    pub cost: E,
+}
+
+impl<E> Path<E> {
+    pub fn source(&self) -> NodeId {
+        self.nodes[0]
+    }
+
+    pub fn target(&self) -> NodeId {
+        self.nodes[self.nodes.len() - 1]
+    }
+
+    // number of edges walked
+    pub fn hops(&self) -> usize {
+        self.nodes.len() - 1
+    }
+}
+
+// node payloads that sit somewhere on a plane, used by the built in A* heuristics
+pub trait Position {
+    fn position(&self) -> (f64, f64);
+}
+
+impl Position for Node {
+    fn position(&self) -> (f64, f64) {
+        (self.x as f64, self.y as f64)
+    }
+}
+
+// estimates of the remaining distance between two positions
+// they assume every unit of distance costs at least one unit of weight
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Heuristic {
+    // only horizontal and vertical moves, what the maze corridors use
+    Manhattan,
+    // straight line distance
+    Euclidean,
+    // horizontal, vertical and diagonal moves, diagonals cost sqrt(2)
+    Octile,
+}
+
+impl Heuristic {
+    pub fn distance<P: Position>(self, from: &P, to: &P) -> f64 {
+        let (x1, y1) = from.position();
+        let (x2, y2) = to.position();
+        let dx = (x1 - x2).abs();
+        let dy = (y1 - y2).abs();
+        match self {
+            Heuristic::Manhattan => dx + dy,
+            Heuristic::Euclidean => (dx * dx + dy * dy).sqrt(),
+            Heuristic::Octile => dx.max(dy) + (std::f64::consts::SQRT_2 - 1.0) * dx.min(dy),
+        }
+    }
+}
+
+// distances and parent links from a single source, filled in by dijkstra_all
+#[derive(Clone, Debug)]
+pub struct ShortestPaths<E> {
+    source: NodeId,
+    distances: Vec<Option<E>>,
+    parents: Vec<Option<NodeId>>,
+}
+
+impl<E: Weight> ShortestPaths<E> {
+    fn new(source: NodeId, node_count: usize) -> ShortestPaths<E> {
+        let mut distances = vec![None; node_count];
+        distances[source.index()] = Some(E::zero());
+        ShortestPaths {
+            source,
+            distances,
+            parents: vec![None; node_count],
+        }
+    }
+
+    pub fn source(&self) -> NodeId {
+        self.source
+    }
+
+    // None if the node can't be reached from the source
+    pub fn distance(&self, node: NodeId) -> Option<E> {
+        self.distances.get(node.index()).copied().flatten()
+    }
+
+    pub fn parent(&self, node: NodeId) -> Option<NodeId> {
+        self.parents.get(node.index()).copied().flatten()
+    }
+
+    pub fn is_reachable(&self, node: NodeId) -> bool {
+        self.distance(node).is_some()
+    }
+
+    pub fn path_to(&self, target: NodeId) -> Option<Path<E>> {
+        let cost = self.distance(target)?;
+        let mut nodes = vec![target];
+        let mut current = target;
+        while let Some(parent) = self.parent(current) {
+            nodes.push(parent);
+            current = parent;
+        }
+        nodes.reverse();
+        Some(Path { nodes, cost })
+    }
+}
+
+impl<N, E: Weight, Ty: EdgeType> Graph<N, E, Ty> {
+    // cheapest path between two nodes, None if either is missing or target can't be reached
+    pub fn dijkstra(&self, source: NodeId, target: NodeId) -> Option<Path<E>> {
+        dijkstra(self, source, target)
+    }
+
+    // cheapest paths from source to every node it can reach
+    pub fn dijkstra_all(&self, source: NodeId) -> Option<ShortestPaths<E>> {
+        dijkstra_all(self, source)
+    }
+
+    // A* search, heuristic estimates the cost left from a node to target and must never overestimate it
+    // nodes aren't reopened once settled, so the estimate also mustn't drop by more than an edge's weight
+    // along that edge, the built in heuristics hold to both when weights are at least the distance covered
+    pub fn astar<H>(&self, source: NodeId, target: NodeId, heuristic: H) -> Option<Path<E>>
+    where
+        H: FnMut(NodeId) -> E,
+    {
+        astar(self, source, target, heuristic)
+    }
+}
+
+impl<N: Position, E: Weight, Ty: EdgeType> Graph<N, E, Ty> {
+    // A* using one of the built in heuristics over the node positions
+    pub fn astar_with(
+        &self,
+        source: NodeId,
+        target: NodeId,
+        heuristic: Heuristic,
+    ) -> Option<Path<E>> {
+        let goal = self.node(target)?;
+        self.astar(source, target, |node| {
+            E::from_distance(heuristic.distance(&self[node], goal))
+        })
+    }
+}
+
+impl<N, E: Weight, Ty: EdgeType> Csr<N, E, Ty> {
+    // see Graph::dijkstra
+    pub fn dijkstra(&self, source: NodeId, target: NodeId) -> Option<Path<E>> {
+        dijkstra(self, source, target)
+    }
+
+    // see Graph::dijkstra_all
+    pub fn dijkstra_all(&self, source: NodeId) -> Option<ShortestPaths<E>> {
+        dijkstra_all(self, source)
+    }
+
+    // see Graph::astar
+    pub fn astar<H>(&self, source: NodeId, target: NodeId, heuristic: H) -> Option<Path<E>>
+    where
+        H: FnMut(NodeId) -> E,
+    {
+        astar(self, source, target, heuristic)
+    }
+}
+
+impl<N: Position, E: Weight, Ty: EdgeType> Csr<N, E, Ty> {
+    // see Graph::astar_with
+    pub fn astar_with(
+        &self,
+        source: NodeId,
+        target: NodeId,
+        heuristic: Heuristic,
+    ) -> Option<Path<E>> {
+        let goal = self.node(target)?;
+        self.astar(source, target, |node| {
+            E::from_distance(heuristic.distance(&self[node], goal))
+        })
+    }
+}
+
+fn dijkstra<G>(graph: &G, source: NodeId, target: NodeId) -> Option<Path<G::Weight>>
+where
+    G: Adjacency,
+    G::Weight: Weight,
+{
+    if !graph.contains_node(source) || !graph.contains_node(target) {
+        return None;
+    }
+    dijkstra_search(graph, source, Some(target)).path_to(target)
+}
+
+fn dijkstra_all<G>(graph: &G, source: NodeId) -> Option<ShortestPaths<G::Weight>>
+where
+    G: Adjacency,
+    G::Weight: Weight,
+{
+    if !graph.contains_node(source) {
+        return None;
+    }
+    Some(dijkstra_search(graph, source, None))
+}
+
+fn astar<G, H>(
+    graph: &G,
+    source: NodeId,
+    target: NodeId,
+    mut heuristic: H,
+) -> Option<Path<G::Weight>>
+where
+    G: Adjacency,
+    G::Weight: Weight,
+    H: FnMut(NodeId) -> G::Weight,
+{
+    if !graph.contains_node(source) || !graph.contains_node(target) {
+        return None;
+    }
+    let mut paths = ShortestPaths::new(source, graph.node_count());
+    let mut settled = vec![false; graph.node_count()];
+    // the heuristic is asked once per node, the built in ones go through f64 each time
+    let mut estimates: Vec<Option<G::Weight>> = vec![None; graph.node_count()];
+    let mut estimate = |node: NodeId| -> G::Weight {
+        *estimates[node.index()].get_or_insert_with(|| heuristic(node))
+    };
+    let mut heap = BinaryHeap::new();
+    // equal estimates go to the node furthest along, it is usually the one closer to target
+    heap.push(Reverse((
+        estimate(source),
+        Reverse(G::Weight::zero()),
+        source,
+    )));
+
+    while let Some(Reverse((_, Reverse(cost), node))) = heap.pop() {
+        if settled[node.index()] {
+            continue;
+        }
+        settled[node.index()] = true;
+        if node == target {
+            return paths.path_to(target);
+        }
+        for edge in graph.edges_from(node) {
+            if settled[edge.to.index()] {
+                continue;
+            }
+            let next_cost = cost + *edge.weight;
+            let better = match paths.distances[edge.to.index()] {
+                None => true,
+                Some(current) => next_cost < current,
+            };
+            if better {
+                paths.distances[edge.to.index()] = Some(next_cost);
+                paths.parents[edge.to.index()] = Some(node);
+                let guess = next_cost + estimate(edge.to);
+                heap.push(Reverse((guess, Reverse(next_cost), edge.to)));
+            }
+        }
+    }
+    None
+}
+
+// stops as soon as target is settled, when there is one
+fn dijkstra_search<G>(graph: &G, source: NodeId, target: Option<NodeId>) -> ShortestPaths<G::Weight>
+where
+    G: Adjacency,
+    G::Weight: Weight,
+{
+    let mut paths = ShortestPaths::new(source, graph.node_count());
+    let mut settled = vec![false; graph.node_count()];
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse((G::Weight::zero(), source)));
+
+    while let Some(Reverse((cost, node))) = heap.pop() {
+        if settled[node.index()] {
+            continue;
+        }
+        settled[node.index()] = true;
+        if Some(node) == target {
+            break;
+        }
+        for edge in graph.edges_from(node) {
+            let next_cost = cost + *edge.weight;
+            let better = match paths.distances[edge.to.index()] {
+                None => true,
+                Some(current) => next_cost < current,
+            };
+            if better && !settled[edge.to.index()] {
+                paths.distances[edge.to.index()] = Some(next_cost);
+                paths.parents[edge.to.index()] = Some(node);
+                heap.push(Reverse((next_cost, edge.to)));
+            }
+        }
+    }
+    paths
+}
@@ -0,0 +1,222 @@
+use super::{EdgeType, Graph, Node, NodeId};
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+use std::ops::RangeInclusive;
+
+// node payloads with a whole number spot on a grid, like the pixels nodes of a maze sit on
+// every node is expected to have its own spot
+pub trait GridPosition {
+    fn grid_position(&self) -> (u32, u32);
+}
+
+impl GridPosition for Node {
+    fn grid_position(&self) -> (u32, u32) {
+        (self.x, self.y)
+    }
+}
+
+// multiply and rotate hash, positions are small integers so the default SipHash is wasted effort
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct PositionHasher(u64);
+
+impl Hasher for PositionHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.write_u64(byte as u64);
+        }
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.write_u64(n as u64);
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.0 = (self.0.rotate_left(5) ^ n).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
+}
+
+type PositionMap = HashMap<(u32, u32), NodeId, BuildHasherDefault<PositionHasher>>;
+
+// position lookup kept up to date by add_node, remove_node, node_mut and IndexMut
+// holds on to the key function so the graph can maintain it without knowing N
+#[derive(Clone, Debug)]
+pub(crate) struct SpatialIndex<N> {
+    key: fn(&N) -> (u32, u32),
+    // nodes handed out mutably, taken out of the table since they may have moved
+    // lookups check them by hand until the next change to the graph puts them back
+    detached: Vec<NodeId>,
+    // dense width * height table of node indices, EMPTY where there is no node
+    grid: Vec<u32>,
+    width: u32,
+    height: u32,
+    // positions outside the table, or every position when there is no table
+    cells: PositionMap,
+}
+
+const EMPTY: u32 = u32::MAX;
+
+impl<N> SpatialIndex<N> {
+    fn new(key: fn(&N) -> (u32, u32), width: u32, height: u32) -> SpatialIndex<N> {
+        SpatialIndex {
+            key,
+            detached: Vec::new(),
+            grid: vec![EMPTY; width as usize * height as usize],
+            width,
+            height,
+            cells: PositionMap::default(),
+        }
+    }
+
+    fn slot(&self, (x, y): (u32, u32)) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+
+    // where nodes are found, the detached ones included
+    fn find(&self, nodes: &[N], position: (u32, u32)) -> Option<NodeId> {
+        let detached = self
+            .detached
+            .iter()
+            .copied()
+            .filter(|id| (self.key)(&nodes[id.0]) == position)
+            .min();
+        match (self.get(position), detached) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (found, None) | (None, found) => found,
+        }
+    }
+
+    fn get(&self, position: (u32, u32)) -> Option<NodeId> {
+        match self.slot(position) {
+            Some(slot) if self.grid[slot] != EMPTY => Some(NodeId(self.grid[slot] as usize)),
+            _ => self.cells.get(&position).copied(),
+        }
+    }
+
+    // first node at a position wins, same as a scan would find
+    fn set(&mut self, position: (u32, u32), id: Option<NodeId>) {
+        match (self.slot(position), id) {
+            (Some(slot), Some(id)) if id.0 < EMPTY as usize => self.grid[slot] = id.0 as u32,
+            (Some(slot), None) => {
+                self.grid[slot] = EMPTY;
+                self.cells.remove(&position);
+            }
+            (None, None) => {
+                self.cells.remove(&position);
+            }
+            (_, Some(id)) => {
+                self.cells.insert(position, id);
+            }
+        }
+    }
+
+    pub fn insert(&mut self, payload: &N, id: NodeId) {
+        let position = (self.key)(payload);
+        if self.get(position).is_none() {
+            self.set(position, Some(id));
+        }
+    }
+
+    pub fn remove(&mut self, payload: &N, id: NodeId) {
+        let position = (self.key)(payload);
+        if self.get(position) == Some(id) {
+            self.set(position, None);
+        }
+    }
+
+    // takes a node out of the table before it is handed out mutably
+    pub fn detach(&mut self, nodes: &[N], id: NodeId) {
+        self.settle(nodes);
+        self.remove(&nodes[id.0], id);
+        self.detached.push(id);
+    }
+
+    // puts the detached nodes back at wherever they sit now, has to happen before the node list changes
+    pub fn settle(&mut self, nodes: &[N]) {
+        while let Some(id) = self.detached.pop() {
+            self.insert(&nodes[id.0], id);
+        }
+    }
+
+    pub fn moved(&mut self, payload: &N, from: NodeId, to: NodeId) {
+        let position = (self.key)(payload);
+        if self.get(position) == Some(from) {
+            self.set(position, Some(to));
+        }
+    }
+}
+
+impl<N: GridPosition, E, Ty: EdgeType> Graph<N, E, Ty> {
+    // turns on the position index, after this find_node_xy is a hash lookup
+    // nodes moved through node_mut or IndexMut are put back at their new spot
+    pub fn build_spatial_index(&mut self) {
+        self.index_positions(SpatialIndex::new(N::grid_position, 0, 0));
+    }
+
+    // same as build_spatial_index but backed by a dense table when the nodes sit on a known grid,
+    // cheaper than hashing when most of the grid is filled, like the pixels of a maze
+    pub fn build_grid_index(&mut self, width: u32, height: u32) {
+        self.index_positions(SpatialIndex::new(N::grid_position, width, height));
+    }
+
+    fn index_positions(&mut self, mut index: SpatialIndex<N>) {
+        for (i, payload) in self.nodes.iter().enumerate() {
+            index.insert(payload, NodeId(i));
+        }
+        self.spatial = Some(index);
+    }
+
+    pub fn drop_spatial_index(&mut self) {
+        self.spatial = None;
+    }
+
+    pub fn has_spatial_index(&self) -> bool {
+        self.spatial.is_some()
+    }
+
+    // constant time with the spatial index, a scan over every node without it
+    pub fn find_node_xy(&self, x: u32, y: u32) -> Option<NodeId> {
+        match &self.spatial {
+            Some(index) => index.find(&self.nodes, (x, y)),
+            None => self.find_node(|node| node.grid_position() == (x, y)),
+        }
+    }
+
+    // every node inside the rectangle, edges included, in no particular order
+    pub fn nodes_in_rect(&self, x: RangeInclusive<u32>, y: RangeInclusive<u32>) -> Vec<NodeId> {
+        if x.is_empty() || y.is_empty() {
+            return Vec::new();
+        }
+        let area = (*x.end() - *x.start()) as u64 + 1;
+        let area = area * ((*y.end() - *y.start()) as u64 + 1);
+        match &self.spatial {
+            // small rectangles are quicker to probe cell by cell than to scan
+            Some(index) if area < self.nodes.len() as u64 => {
+                let mut found = Vec::new();
+                for cell_y in y {
+                    for cell_x in x.clone() {
+                        if let Some(id) = index.find(&self.nodes, (cell_x, cell_y)) {
+                            found.push(id);
+                        }
+                    }
+                }
+                found
+            }
+            _ => self
+                .nodes()
+                .filter(|(_, node)| {
+                    let (node_x, node_y) = node.grid_position();
+                    x.contains(&node_x) && y.contains(&node_y)
+                })
+                .map(|(id, _)| id)
+                .collect(),
+        }
+    }
+}
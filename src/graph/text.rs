@@ -0,0 +1,124 @@
+use super::{EdgeType, Graph, NodeId, ParseError};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+// strips a # comment and surrounding whitespace
+fn content(line: &str) -> &str {
+    match line.find('#') {
+        Some(start) => line[..start].trim(),
+        None => line.trim(),
+    }
+}
+
+// builds a graph of named nodes, creating each node the first time its name shows up
+struct NamedGraph<E, Ty> {
+    graph: Graph<String, E, Ty>,
+    ids: HashMap<String, NodeId>,
+}
+
+impl<E, Ty: EdgeType> NamedGraph<E, Ty> {
+    fn new() -> Self {
+        NamedGraph {
+            graph: Graph::default(),
+            ids: HashMap::new(),
+        }
+    }
+
+    fn node(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+        let id = self.graph.add_node(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        id
+    }
+}
+
+fn parse_weight<E: FromStr + Default>(text: Option<&str>, line: usize) -> Result<E, ParseError> {
+    match text {
+        None => Ok(E::default()),
+        Some(text) => text
+            .parse()
+            .map_err(|_| ParseError::new(line, format!("{:?} is not a valid weight", text))),
+    }
+}
+
+impl<E: FromStr + Default, Ty: EdgeType> Graph<String, E, Ty> {
+    // one edge per line as `from to [weight]`, a name on its own adds a node without edges
+    // missing weights are E::default(), # starts a comment
+    //
+    //     # corridors
+    //     a b 20
+    //     a c 2
+    //     d
+    pub fn from_edge_list(text: &str) -> Result<Self, ParseError> {
+        let mut named = NamedGraph::new();
+        for (i, line) in text.lines().enumerate() {
+            let line_number = i + 1;
+            let fields: Vec<&str> = content(line).split_whitespace().collect();
+            match fields.len() {
+                0 => {}
+                1 => {
+                    named.node(fields[0]);
+                }
+                2 | 3 => {
+                    let weight = parse_weight(fields.get(2).copied(), line_number)?;
+                    let from = named.node(fields[0]);
+                    let to = named.node(fields[1]);
+                    named.graph.add_edge(from, to, weight);
+                }
+                n => {
+                    return Err(ParseError::new(
+                        line_number,
+                        format!("expected `from to [weight]` but found {} fields", n),
+                    ))
+                }
+            }
+        }
+        Ok(named.graph)
+    }
+
+    // one node per line followed by a colon and its neighbors, each with an optional =weight
+    // missing weights are E::default(), # starts a comment
+    //
+    //     a: b=20 c=2
+    //     c: d
+    //     d:
+    pub fn from_adjacency_list(text: &str) -> Result<Self, ParseError> {
+        let mut named = NamedGraph::new();
+        for (i, line) in text.lines().enumerate() {
+            let line_number = i + 1;
+            let line = content(line);
+            if line.is_empty() {
+                continue;
+            }
+            let (name, neighbors) = match line.find(':') {
+                Some(colon) => (line[..colon].trim(), &line[colon + 1..]),
+                None => {
+                    return Err(ParseError::new(
+                        line_number,
+                        "expected `node: neighbor[=weight] ...`",
+                    ))
+                }
+            };
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return Err(ParseError::new(
+                    line_number,
+                    format!("{:?} is not a valid node name", name),
+                ));
+            }
+            let from = named.node(name);
+            for neighbor in neighbors.split_whitespace() {
+                let mut parts = neighbor.splitn(2, '=');
+                let to_name = parts.next().unwrap_or("");
+                if to_name.is_empty() {
+                    return Err(ParseError::new(line_number, "neighbor without a name"));
+                }
+                let weight = parse_weight(parts.next(), line_number)?;
+                let to = named.node(to_name);
+                named.graph.add_edge(from, to, weight);
+            }
+        }
+        Ok(named.graph)
+    }
+}
@@ -0,0 +1,251 @@
+use super::{Adjacency, Csr, EdgeRef, EdgeType, Graph, NodeId};
+use std::collections::VecDeque;
+
+// breadth first walk that hands out nodes one at a time as they are dequeued
+pub struct Bfs<'a, G> {
+    graph: &'a G,
+    visited: Vec<bool>,
+    queue: VecDeque<NodeId>,
+}
+
+impl<'a, G: Adjacency> Bfs<'a, G> {
+    // yields nothing if start is missing
+    pub fn new(graph: &'a G, start: NodeId) -> Bfs<'a, G> {
+        let mut visited = vec![false; graph.node_count()];
+        let mut queue = VecDeque::new();
+        if graph.contains_node(start) {
+            visited[start.index()] = true;
+            queue.push_back(start);
+        }
+        Bfs {
+            graph,
+            visited,
+            queue,
+        }
+    }
+}
+
+impl<'a, G: Adjacency> Iterator for Bfs<'a, G> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let current_node = self.queue.pop_front()?;
+        // get all the adj vertices of that node
+        for edge in self.graph.edges_from(current_node) {
+            if !self.visited[edge.to.index()] {
+                self.visited[edge.to.index()] = true;
+                self.queue.push_back(edge.to);
+            }
+        }
+        Some(current_node)
+    }
+}
+
+// depth first walk, neighbors go on a stack so the last one listed is walked first
+pub struct Dfs<'a, G> {
+    graph: &'a G,
+    visited: Vec<bool>,
+    stack: Vec<NodeId>,
+}
+
+impl<'a, G: Adjacency> Dfs<'a, G> {
+    // yields nothing if start is missing
+    pub fn new(graph: &'a G, start: NodeId) -> Dfs<'a, G> {
+        let stack = if graph.contains_node(start) {
+            vec![start]
+        } else {
+            Vec::new()
+        };
+        Dfs {
+            graph,
+            visited: vec![false; graph.node_count()],
+            stack,
+        }
+    }
+}
+
+impl<'a, G: Adjacency> Iterator for Dfs<'a, G> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        while let Some(current_node) = self.stack.pop() {
+            if !self.visited[current_node.index()] {
+                self.visited[current_node.index()] = true;
+                self.stack
+                    .extend(self.graph.edges_from(current_node).map(|e| e.to));
+                return Some(current_node);
+            }
+        }
+        None
+    }
+}
+
+// what a visitor wants the search to do after one of its hooks
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    // from discover the node's edges are skipped, from examine_edge the edge isn't followed
+    Prune,
+    // stop the whole search
+    Break,
+}
+
+// hooks called by bfs_visit and dfs_visit, the ones left out just continue
+pub trait Visitor<E> {
+    // first time the search reaches a node, via is the edge it came over, None for the start
+    fn discover(&mut self, _node: NodeId, _via: Option<EdgeRef<'_, E>>) -> Control {
+        Control::Continue
+    }
+
+    // every edge leaving a node the search expands, before looking at where it goes
+    fn examine_edge(&mut self, _edge: EdgeRef<'_, E>) -> Control {
+        Control::Continue
+    }
+
+    // all of a node's edges are done, for depth first that includes everything below it
+    fn finish(&mut self, _node: NodeId) -> Control {
+        Control::Continue
+    }
+}
+
+// Break if the visitor stopped the search early, nothing is visited if start is missing
+pub(super) fn breadth_first_visit<G, V>(graph: &G, start: NodeId, visitor: &mut V) -> Control
+where
+    G: Adjacency,
+    V: Visitor<G::Weight>,
+{
+    if !graph.contains_node(start) {
+        return Control::Continue;
+    }
+    let mut discovered = vec![false; graph.node_count()];
+    let mut queue = VecDeque::new();
+
+    discovered[start.index()] = true;
+    match visitor.discover(start, None) {
+        Control::Break => return Control::Break,
+        Control::Prune => return visitor.finish(start),
+        Control::Continue => queue.push_back(start),
+    }
+
+    while let Some(node) = queue.pop_front() {
+        for edge in graph.edges_from(node) {
+            match visitor.examine_edge(edge) {
+                Control::Break => return Control::Break,
+                Control::Prune => continue,
+                Control::Continue => {}
+            }
+            if discovered[edge.to.index()] {
+                continue;
+            }
+            discovered[edge.to.index()] = true;
+            match visitor.discover(edge.to, Some(edge)) {
+                Control::Break => return Control::Break,
+                Control::Prune => {
+                    if visitor.finish(edge.to) == Control::Break {
+                        return Control::Break;
+                    }
+                }
+                Control::Continue => queue.push_back(edge.to),
+            }
+        }
+        if visitor.finish(node) == Control::Break {
+            return Control::Break;
+        }
+    }
+    Control::Continue
+}
+
+// Break if the visitor stopped the search early, nothing is visited if start is missing
+// neighbors are walked in the order they are listed, unlike Dfs which takes them off a stack
+pub(super) fn depth_first_visit<G, V>(graph: &G, start: NodeId, visitor: &mut V) -> Control
+where
+    G: Adjacency,
+    V: Visitor<G::Weight>,
+{
+    if !graph.contains_node(start) {
+        return Control::Continue;
+    }
+    let mut discovered = vec![false; graph.node_count()];
+
+    discovered[start.index()] = true;
+    match visitor.discover(start, None) {
+        Control::Break => return Control::Break,
+        Control::Prune => return visitor.finish(start),
+        Control::Continue => {}
+    }
+    // the nodes on the current path with the edges they have left to look at
+    let mut stack = vec![(start, graph.edges_from(start))];
+
+    while let Some((node, edges)) = stack.last_mut() {
+        let edge = match edges.next() {
+            Some(edge) => edge,
+            None => {
+                let node = *node;
+                stack.pop();
+                if visitor.finish(node) == Control::Break {
+                    return Control::Break;
+                }
+                continue;
+            }
+        };
+        match visitor.examine_edge(edge) {
+            Control::Break => return Control::Break,
+            Control::Prune => continue,
+            Control::Continue => {}
+        }
+        if discovered[edge.to.index()] {
+            continue;
+        }
+        discovered[edge.to.index()] = true;
+        match visitor.discover(edge.to, Some(edge)) {
+            Control::Break => return Control::Break,
+            Control::Prune => {
+                if visitor.finish(edge.to) == Control::Break {
+                    return Control::Break;
+                }
+            }
+            Control::Continue => stack.push((edge.to, graph.edges_from(edge.to))),
+        }
+    }
+    Control::Continue
+}
+
+impl<N, E, Ty: EdgeType> Graph<N, E, Ty> {
+    pub fn bfs(&self, start: NodeId) -> Bfs<'_, Self> {
+        Bfs::new(self, start)
+    }
+
+    pub fn dfs(&self, start: NodeId) -> Dfs<'_, Self> {
+        Dfs::new(self, start)
+    }
+
+    // breadth first search that reports what it does to the visitor
+    pub fn bfs_visit<V: Visitor<E>>(&self, start: NodeId, visitor: &mut V) -> Control {
+        breadth_first_visit(self, start, visitor)
+    }
+
+    // depth first search that reports what it does to the visitor
+    pub fn dfs_visit<V: Visitor<E>>(&self, start: NodeId, visitor: &mut V) -> Control {
+        depth_first_visit(self, start, visitor)
+    }
+}
+
+impl<N, E, Ty: EdgeType> Csr<N, E, Ty> {
+    pub fn bfs(&self, start: NodeId) -> Bfs<'_, Self> {
+        Bfs::new(self, start)
+    }
+
+    pub fn dfs(&self, start: NodeId) -> Dfs<'_, Self> {
+        Dfs::new(self, start)
+    }
+
+    // see Graph::bfs_visit
+    pub fn bfs_visit<V: Visitor<E>>(&self, start: NodeId, visitor: &mut V) -> Control {
+        breadth_first_visit(self, start, visitor)
+    }
+
+    // see Graph::dfs_visit
+    pub fn dfs_visit<V: Visitor<E>>(&self, start: NodeId, visitor: &mut V) -> Control {
+        depth_first_visit(self, start, visitor)
+    }
+}
@@ -0,0 +1,299 @@
+use super::{Adjacency, Csr, EdgeId, EdgeType, Graph, NodeId, Path, Weight};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet};
+
+// a path that remembers its edges and the cost of reaching each of its nodes
+// ordered cheapest first, fewer hops and then node order break ties so runs are repeatable
+struct Route<E> {
+    nodes: Vec<NodeId>,
+    edges: Vec<EdgeId>,
+    // costs[i] is the cost from the first node to nodes[i]
+    costs: Vec<E>,
+}
+
+impl<E: Copy> Route<E> {
+    fn cost(&self) -> E {
+        self.costs[self.costs.len() - 1]
+    }
+
+    fn into_path(self) -> Path<E> {
+        let cost = self.cost();
+        Path {
+            nodes: self.nodes,
+            cost,
+        }
+    }
+}
+
+impl<E: Copy + Ord> Ord for Route<E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.cost(), self.nodes.len(), &self.nodes).cmp(&(
+            other.cost(),
+            other.nodes.len(),
+            &other.nodes,
+        ))
+    }
+}
+
+impl<E: Copy + Ord> PartialOrd for Route<E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E: Copy + Ord> PartialEq for Route<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<E: Copy + Ord> Eq for Route<E> {}
+
+// Yen's algorithm, up to k cheapest paths from source to target that never visit a node twice
+// each new path branches off an earlier one at some node and then takes the cheapest way
+// to the target that avoids the earlier paths' next edges and the nodes before the branch
+fn k_shortest_paths<G>(graph: &G, source: NodeId, target: NodeId, k: usize) -> Vec<Path<G::Weight>>
+where
+    G: Adjacency,
+    G::Weight: Weight,
+{
+    if k == 0 || !graph.contains_node(source) || !graph.contains_node(target) {
+        return Vec::new();
+    }
+    let mut search = SpurSearch::new(graph, target);
+    let first = match search.run(graph, source, target, G::Weight::zero(), None) {
+        Some(route) => route,
+        None => return Vec::new(),
+    };
+    let mut found = vec![first];
+    let mut candidates: BinaryHeap<Reverse<Route<G::Weight>>> = BinaryHeap::new();
+    // node sequences already found or waiting, so no path is offered twice
+    let mut seen: HashSet<Vec<NodeId>> = HashSet::new();
+    seen.insert(found[0].nodes.clone());
+
+    while found.len() < k {
+        let wanted = k - found.len();
+        // costs of the cheapest candidates that can still be picked, the dearest on top
+        // once there are enough of them a spur costing as much as the top can't make the cut,
+        // between paths of equal cost the one found first stays
+        let mut bounds: BinaryHeap<G::Weight> = candidates
+            .iter()
+            .map(|Reverse(route)| route.cost())
+            .collect();
+        while bounds.len() > wanted {
+            bounds.pop();
+        }
+
+        let last = &found[found.len() - 1];
+        // found paths that start out the same way as last up to the spur node
+        let mut sharing: Vec<usize> = (0..found.len()).collect();
+        for i in 0..last.nodes.len() - 1 {
+            // the root grows by a node each step, so the blocked nodes and sharing paths follow along
+            if i > 0 {
+                search.blocked_nodes[last.nodes[i - 1].index()] = true;
+                sharing.retain(|&j| found[j].nodes.get(i) == Some(&last.nodes[i]));
+            }
+            let limit = bounds.peek().copied().filter(|_| bounds.len() == wanted);
+            // the root's cost plus the distance left with nothing blocked is the least a spur from here costs
+            let least = search.remaining[last.nodes[i].index()].map(|left| last.costs[i] + left);
+            if least.is_none_or(|least| limit.is_some_and(|limit| least >= limit)) {
+                continue;
+            }
+
+            search.blocked_edges.clear();
+            for &j in &sharing {
+                if let Some(&edge) = found[j].edges.get(i) {
+                    search.blocked_edges.insert(edge);
+                }
+            }
+            let spur = search.run(graph, last.nodes[i], target, last.costs[i], limit);
+
+            if let Some(spur) = spur {
+                let mut route = Route {
+                    nodes: last.nodes[..i].to_vec(),
+                    edges: last.edges[..i].to_vec(),
+                    costs: last.costs[..i].to_vec(),
+                };
+                route.nodes.extend(spur.nodes);
+                route.edges.extend(spur.edges);
+                route.costs.extend(spur.costs);
+                if seen.insert(route.nodes.clone()) {
+                    bounds.push(route.cost());
+                    if bounds.len() > wanted {
+                        bounds.pop();
+                    }
+                    candidates.push(Reverse(route));
+                }
+            }
+        }
+
+        for node in &last.nodes {
+            search.blocked_nodes[node.index()] = false;
+        }
+
+        match candidates.pop() {
+            Some(Reverse(route)) => found.push(route),
+            None => break,
+        }
+    }
+    found.into_iter().map(Route::into_path).collect()
+}
+
+// cost of the cheapest way from every node to target, None where target can't be reached
+fn distances_to<G>(graph: &G, target: NodeId) -> Vec<Option<G::Weight>>
+where
+    G: Adjacency,
+    G::Weight: Weight,
+{
+    let mut distances = vec![None; graph.node_count()];
+    let mut settled = vec![false; graph.node_count()];
+    let mut heap = BinaryHeap::new();
+    distances[target.index()] = Some(G::Weight::zero());
+    heap.push(Reverse((G::Weight::zero(), target)));
+
+    while let Some(Reverse((cost, node))) = heap.pop() {
+        if settled[node.index()] {
+            continue;
+        }
+        settled[node.index()] = true;
+        for edge in graph.edges_to(node) {
+            let next_cost = cost + *edge.weight;
+            let from = edge.from.index();
+            if !settled[from] && distances[from].is_none_or(|current| next_cost < current) {
+                distances[from] = Some(next_cost);
+                heap.push(Reverse((next_cost, edge.from)));
+            }
+        }
+    }
+    distances
+}
+
+// buffers for the spur searches, kept between them because there is one per node of every path found
+// only the entries a search touched get cleared, so a short detour doesn't pay for the whole graph
+struct SpurSearch<E> {
+    // distances to target with nothing blocked, an exact A* estimate until something is
+    remaining: Vec<Option<E>>,
+    distances: Vec<Option<E>>,
+    parents: Vec<Option<(NodeId, EdgeId)>>,
+    settled: Vec<bool>,
+    touched: Vec<NodeId>,
+    heap: BinaryHeap<Reverse<(E, Reverse<E>, NodeId)>>,
+    blocked_nodes: Vec<bool>,
+    blocked_edges: HashSet<EdgeId>,
+}
+
+impl<E: Weight> SpurSearch<E> {
+    fn new<G>(graph: &G, target: NodeId) -> SpurSearch<E>
+    where
+        G: Adjacency<Weight = E>,
+    {
+        let node_count = graph.node_count();
+        SpurSearch {
+            remaining: distances_to(graph, target),
+            distances: vec![None; node_count],
+            parents: vec![None; node_count],
+            settled: vec![false; node_count],
+            touched: Vec::new(),
+            heap: BinaryHeap::new(),
+            blocked_nodes: vec![false; node_count],
+            blocked_edges: HashSet::new(),
+        }
+    }
+
+    // A* from spur to target around the blocked nodes and edges, costs start at offset
+    // blocking only makes the remaining distances an underestimate, so nodes are still settled once
+    // gives up with None as soon as every way left costs limit or more
+    fn run<G>(
+        &mut self,
+        graph: &G,
+        spur: NodeId,
+        target: NodeId,
+        offset: E,
+        limit: Option<E>,
+    ) -> Option<Route<E>>
+    where
+        G: Adjacency<Weight = E>,
+    {
+        for node in self.touched.drain(..) {
+            self.distances[node.index()] = None;
+            self.parents[node.index()] = None;
+            self.settled[node.index()] = false;
+        }
+        self.heap.clear();
+        let estimate = offset + self.remaining[spur.index()]?;
+        self.distances[spur.index()] = Some(offset);
+        self.touched.push(spur);
+        // equal estimates go to the node furthest along, with exact estimates that is the one on the route
+        self.heap.push(Reverse((estimate, Reverse(offset), spur)));
+
+        while let Some(Reverse((estimate, Reverse(cost), node))) = self.heap.pop() {
+            if self.settled[node.index()] {
+                continue;
+            }
+            if limit.is_some_and(|limit| estimate >= limit) {
+                return None;
+            }
+            self.settled[node.index()] = true;
+            if node == target {
+                break;
+            }
+            for edge in graph.edges_from(node) {
+                let next = edge.to.index();
+                if self.settled[next]
+                    || self.blocked_nodes[next]
+                    || self.blocked_edges.contains(&edge.id)
+                {
+                    continue;
+                }
+                // nodes that can't get to target are never worth a visit
+                let left = match self.remaining[next] {
+                    Some(left) => left,
+                    None => continue,
+                };
+                let next_cost = cost + *edge.weight;
+                if self.distances[next].is_none_or(|current| next_cost < current) {
+                    if self.distances[next].is_none() {
+                        self.touched.push(edge.to);
+                    }
+                    self.distances[next] = Some(next_cost);
+                    self.parents[next] = Some((node, edge.id));
+                    self.heap
+                        .push(Reverse((next_cost + left, Reverse(next_cost), edge.to)));
+                }
+            }
+        }
+
+        let mut route = Route {
+            nodes: vec![target],
+            edges: Vec::new(),
+            costs: vec![self.distances[target.index()]?],
+        };
+        let mut current = target;
+        while let Some((parent, edge)) = self.parents[current.index()] {
+            route.nodes.push(parent);
+            route.edges.push(edge);
+            route.costs.push(self.distances[parent.index()].unwrap());
+            current = parent;
+        }
+        route.nodes.reverse();
+        route.edges.reverse();
+        route.costs.reverse();
+        Some(route)
+    }
+}
+
+impl<N, E: Weight, Ty: EdgeType> Graph<N, E, Ty> {
+    // up to k cheapest paths that don't visit any node twice, cheapest first
+    // fewer than k when there aren't that many, empty if either node is missing or target can't be reached
+    // when more paths tie for the last places than fit, the ones found first are kept
+    pub fn k_shortest_paths(&self, source: NodeId, target: NodeId, k: usize) -> Vec<Path<E>> {
+        k_shortest_paths(self, source, target, k)
+    }
+}
+
+impl<N, E: Weight, Ty: EdgeType> Csr<N, E, Ty> {
+    // see Graph::k_shortest_paths
+    pub fn k_shortest_paths(&self, source: NodeId, target: NodeId, k: usize) -> Vec<Path<E>> {
+        k_shortest_paths(self, source, target, k)
+    }
+}
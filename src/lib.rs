@@ -82,16 +82,16 @@ pub mod maze_solver {
 
         fn apply_solved_maze_to_buf(&mut self) {
             for cell in self.solved.iter() {
-                self.image_buffer[(cell.vec_coord * 3) as usize] = 0;
-                self.image_buffer[(cell.vec_coord * 3 + 1) as usize] = 0;
-                self.image_buffer[(cell.vec_coord * 3 + 2) as usize] = 255;
+                self.image_buffer[cell.vec_coord * 3] = 0;
+                self.image_buffer[cell.vec_coord * 3 + 1] = 0;
+                self.image_buffer[cell.vec_coord * 3 + 2] = 255;
             }
         }
 
-        pub fn write_image(&mut self, filename: &String) {
-            let path = Path::new(filename.as_str());
+        pub fn write_image(&mut self, filename: &str) {
+            let path = Path::new(filename);
             let file = File::create(path).unwrap();
-            let ref mut w = BufWriter::new(file);
+            let w = &mut BufWriter::new(file);
 
             let mut encoder = png::Encoder::new(w, self.maze.width, self.maze.height);
             encoder.set_color(png::ColorType::RGB);
@@ -117,34 +117,1354 @@ pub mod maze_solver {
 #[cfg(test)]
 mod tests {
     use super::graph::*;
+
+    fn names<Ty: EdgeType>(graph: &Graph<String, u32, Ty>, order: Vec<NodeId>) -> String {
+        let mut result = String::new();
+        for node in order {
+            result.push_str(format!("{} ", graph[node]).as_str());
+        }
+        result
+    }
+
     #[test]
     fn graph_test() {
-        // let mut graph: Graph = Graph::new();
-        // let a = graph.add_node(String::from("a"));
-        // let b = graph.add_node(String::from("b"));
-        // let c = graph.add_node(String::from("c"));
-        // let d = graph.add_node(String::from("d"));
-        // assert!(graph.check_if_node_exist(&a));
-        // // println!("does a exist {}", graph.check_if_node_exist(&a));
-        // graph.add_edge_by_index(0, 1, 5);
-        // graph.add_edge(&a, &b, 20);
-        // graph.add_edge(&a, &c, 2);
-        // graph.add_edge(&b, &c, 5);
-        // graph.add_edge(&c, &a, 5);
-        // graph.add_edge(&c, &d, 5);
-        // graph.add_edge(&d, &d, 5);
-        // let expected_bft = String::from("c a d b ");
-        // let mut bft_result = String::new();
-        // for node in graph.bft(&c).unwrap() {
-        //     bft_result.push_str(format!("{} ", node.name).as_str());
-        // }
-        // assert!(expected_bft.eq(&bft_result));
-
-        // let expected_dft = String::from("c d a b ");
-        // let mut dft_result = String::new();
-        // for node in graph.dft(&c).unwrap() {
-        //     dft_result.push_str(format!("{} ", node.name).as_str());
-        // }
-        // assert!(expected_dft.eq(&dft_result));
+        let mut graph: Graph<String, u32> = Graph::new();
+        let a = graph.add_node(String::from("a"));
+        let b = graph.add_node(String::from("b"));
+        let c = graph.add_node(String::from("c"));
+        let d = graph.add_node(String::from("d"));
+        assert!(graph.check_if_node_exist(a));
+        graph.add_edge_by_index(0, 1, 5);
+        graph.add_edge(a, b, 20);
+        graph.add_edge(a, c, 2);
+        graph.add_edge(b, c, 5);
+        graph.add_edge(c, a, 5);
+        graph.add_edge(c, d, 5);
+        graph.add_edge(d, d, 5);
+
+        // re-adding an edge overwrites its weight instead of duplicating it
+        assert_eq!(graph.edge_count(), 5);
+        assert_eq!(graph[graph.find_edge(a, b).unwrap()].weight, 20);
+        assert_eq!(graph[graph.find_edge(a, c).unwrap()].weight, 5);
+        let ab = graph.find_edge(a, b).unwrap();
+        *graph.edge_weight_mut(ab).unwrap() = 7;
+        assert_eq!(graph[ab].weight, 7);
+        assert!(graph.edge_weight_mut(EdgeId::new(9)).is_none());
+
+        assert_eq!(names(&graph, graph.bft(c).unwrap()), "c a b d ");
+        assert_eq!(names(&graph, graph.dft(c).unwrap()), "c d b a ");
+        assert!(graph.bft(NodeId::new(4)).is_none());
+    }
+
+    #[test]
+    fn directed_graph_test() {
+        let mut graph: DiGraph<&str, u32> = Graph::new_directed();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        graph.add_edge(a, b, 1);
+        graph.add_edge(b, c, 1);
+        assert!(graph.is_directed());
+
+        // one way corridors can't be walked backwards
+        assert_eq!(graph.neighbors(b).collect::<Vec<_>>(), vec![c]);
+        assert_eq!(graph.predecessors(b).collect::<Vec<_>>(), vec![a]);
+        assert!(graph.find_edge(b, a).is_none());
+        assert_eq!(graph.bft(a).unwrap(), vec![a, b, c]);
+        assert_eq!(graph.bft(c).unwrap(), vec![c]);
+
+        // adding the reverse direction makes a separate edge
+        graph.add_edge(b, a, 3);
+        assert_eq!(graph.edge_count(), 3);
+        assert_eq!(graph.dft(c).unwrap(), vec![c]);
+        assert_eq!(graph.dft(b).unwrap(), vec![b, a, c]);
+
+        let mut undirected: UnGraph<&str, u32> = Graph::new();
+        let a = undirected.add_node("a");
+        let b = undirected.add_node("b");
+        undirected.add_edge(a, b, 1);
+        undirected.add_edge(b, a, 2);
+        assert_eq!(undirected.edge_count(), 1);
+        assert_eq!(undirected.neighbors(b).collect::<Vec<_>>(), vec![a]);
+        assert_eq!(undirected[undirected.find_edge(a, b).unwrap()].weight, 2);
+    }
+
+    #[test]
+    fn dijkstra_test() {
+        let mut graph: Graph<&str, u32> = Graph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        let lonely = graph.add_node("lonely");
+        graph.add_edge(a, d, 10);
+        graph.add_edge(a, b, 2);
+        graph.add_edge(b, c, 3);
+        graph.add_edge(c, d, 1);
+
+        // fewest hops would be a -> d, but the longer route is cheaper
+        let path = graph.dijkstra(a, d).unwrap();
+        assert_eq!(path.nodes, vec![a, b, c, d]);
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.hops(), 3);
+        assert!(graph.dijkstra(a, lonely).is_none());
+
+        let all = graph.dijkstra_all(d).unwrap();
+        assert_eq!(all.distance(a), Some(6));
+        assert_eq!(all.distance(d), Some(0));
+        assert_eq!(all.distance(lonely), None);
+        assert_eq!(all.path_to(b).unwrap().nodes, vec![d, c, b]);
+        assert_eq!(graph.dijkstra(a, a).unwrap().nodes, vec![a]);
+    }
+
+    #[test]
+    fn astar_test() {
+        use super::opt_maze::OptMaze;
+
+        // 3x3 grid of nodes with unit length edges, the middle node is missing
+        let mut graph: Graph<Node, u32> = Graph::new();
+        let mut ids = Vec::new();
+        for y in 0..3 {
+            for x in 0..3 {
+                ids.push(graph.add_node(Node::from(x, y, (y * 3 + x) as usize)));
+            }
+        }
+        for y in 0..3 {
+            for x in 0..3 {
+                if (x, y) == (1, 1) {
+                    continue;
+                }
+                if x < 2 && (x + 1, y) != (1, 1) {
+                    graph.add_edge(ids[y * 3 + x], ids[y * 3 + x + 1], 1);
+                }
+                if y < 2 && (x, y + 1) != (1, 1) {
+                    graph.add_edge(ids[y * 3 + x], ids[(y + 1) * 3 + x], 1);
+                }
+            }
+        }
+        let (start, goal) = (ids[0], ids[8]);
+        let expected = graph.dijkstra(start, goal).unwrap();
+        assert_eq!(expected.cost, 4);
+        for heuristic in [
+            Heuristic::Manhattan,
+            Heuristic::Euclidean,
+            Heuristic::Octile,
+        ] {
+            let path = graph.astar_with(start, goal, heuristic).unwrap();
+            assert_eq!(path.cost, expected.cost);
+            assert_eq!(path.source(), start);
+            assert_eq!(path.target(), goal);
+        }
+        // a zero heuristic turns it back into dijkstra
+        assert_eq!(graph.astar(start, goal, |_| 0).unwrap().cost, 4);
+        assert!(graph.astar(start, ids[4], |_| 0).is_none());
+        assert_eq!(
+            Heuristic::Octile.distance(&Node::from(0, 0, 0), &Node::from(3, 1, 0)),
+            2.0 + std::f64::consts::SQRT_2
+        );
+
+        // corridor weights are their length in pixels, so Manhattan never overestimates in a maze
+        let rows = [
+            "#####.###",
+            "#.......#",
+            "#.#####..",
+            "#.......#",
+            "#####.###",
+            "###.....#",
+            "#####.###",
+            "#####.###",
+            "#####.###",
+        ];
+        let maze = OptMaze::from(&maze_image(&rows), 9, 9, true);
+        let graph = maze.graph();
+        for (_, edge) in graph.edges() {
+            let (from, to) = (graph[edge.from], graph[edge.to]);
+            assert_eq!(edge.weight, from.x.abs_diff(to.x) + from.y.abs_diff(to.y));
+        }
+        let path = maze.solve_astar(Heuristic::Manhattan).unwrap();
+        assert_eq!(path, maze.solve().unwrap());
+        assert_eq!(path.cost, 12);
+    }
+
+    #[test]
+    fn spanning_tree_test() {
+        let mut graph: Graph<&str, u32> = Graph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        let e = graph.add_node("e");
+        let f = graph.add_node("f");
+        let ab = graph.add_edge(a, b, 4);
+        graph.add_edge(a, c, 5);
+        let bc = graph.add_edge(b, c, 1);
+        let cd = graph.add_edge(c, d, 2);
+        graph.add_edge(b, d, 6);
+        // e and f are their own component
+        let ef = graph.add_edge(e, f, 7);
+
+        let mut kruskal = graph.kruskal();
+        let mut prim = graph.prim();
+        assert_eq!(kruskal.weight, 14);
+        assert_eq!(prim.weight, 14);
+        kruskal.edges.sort();
+        prim.edges.sort();
+        assert_eq!(kruskal.edges, vec![ab, bc, cd, ef]);
+        assert_eq!(prim.edges, kruskal.edges);
+    }
+
+    #[test]
+    fn components_test() {
+        let mut graph: DiGraph<&str, u32> = Graph::new_directed();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        let e = graph.add_node("e");
+        graph.add_edge(b, a, 1);
+        graph.add_edge(d, c, 1);
+        graph.add_edge(c, e, 1);
+
+        let components = graph.connected_components();
+        assert_eq!(components.count(), 2);
+        assert_eq!(components.labels(), &[0, 0, 1, 1, 1]);
+        assert_eq!(components.sizes(), &[2, 3]);
+        assert!(components.same_component(a, b));
+        assert!(components.same_component(e, d));
+        assert!(!components.same_component(a, e));
+        assert_eq!(components.nodes_in(0), vec![a, b]);
+        assert!(!graph.is_connected());
+
+        let mut sets = UnionFind::new(3);
+        assert!(sets.union(0, 2));
+        assert!(!sets.union(2, 0));
+        assert!(sets.same_set(0, 2));
+        assert_eq!(sets.set_size(2), 2);
+        let x = sets.make_set();
+        assert_eq!(sets.set_count(), 3);
+        assert!(!sets.same_set(x, 1));
+    }
+
+    #[test]
+    fn solvable_test() {
+        use super::opt_maze::OptMaze;
+
+        // a straight corridor down the middle column
+        let mut image = vec![0u8; 25];
+        for y in 0..5 {
+            image[y * 5 + 2] = 255;
+        }
+        let maze = OptMaze::from(&image, 5, 5, true);
+        assert!(maze.end().is_some());
+        assert!(!maze.graph().has_spatial_index());
+        assert!(maze.is_solvable());
+        assert_eq!(maze.solve().unwrap().cost, 4);
+        assert_eq!(maze.disjoint_routes(), 1);
+
+        // the same corridor cut by a wall, the exit gets no node
+        image[2 * 5 + 2] = 0;
+        let maze = OptMaze::from(&image, 5, 5, true);
+        assert_eq!(maze.end(), None);
+        assert!(!maze.is_solvable());
+        assert_eq!(maze.solve(), None);
+        assert_eq!(maze.solve_astar(Heuristic::Manhattan), None);
+        assert_eq!(maze.solve_bidirectional(), None);
+        assert!(maze.solve_k_shortest(3).is_empty());
+        assert_eq!(maze.disjoint_routes(), 0);
+    }
+
+    #[test]
+    fn strongly_connected_test() {
+        // a <-> b -> c <-> d -> e, e is a dead end you can't get out of
+        let mut graph: DiGraph<&str, u32> = Graph::new_directed();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        let e = graph.add_node("e");
+        graph.add_edge(a, b, 1);
+        graph.add_edge(b, a, 1);
+        let bc = graph.add_edge(b, c, 1);
+        let ad = graph.add_edge(a, d, 1);
+        graph.add_edge(c, d, 1);
+        graph.add_edge(d, c, 1);
+        graph.add_edge(d, e, 1);
+
+        let scc = graph.strongly_connected_components();
+        assert_eq!(scc.count(), 3);
+        assert_eq!(scc.labels(), &[0, 0, 1, 1, 2]);
+        assert!(!graph.is_strongly_connected());
+
+        let dag = graph.condensation();
+        assert_eq!(dag.node_count(), 3);
+        assert_eq!(dag[NodeId::new(1)], vec![c, d]);
+        assert_eq!(dag.edge_count(), 2);
+        let crossing = dag.find_edge(NodeId::new(0), NodeId::new(1)).unwrap();
+        assert_eq!(dag[crossing].weight, vec![bc, ad]);
+        let traps: Vec<NodeId> = dag.node_ids().filter(|&n| dag.out_degree(n) == 0).collect();
+        assert_eq!(traps, vec![NodeId::new(2)]);
+    }
+
+    #[test]
+    fn cycle_test() {
+        let mut graph: DiGraph<&str, u32> = Graph::new_directed();
+        let shirt = graph.add_node("shirt");
+        let tie = graph.add_node("tie");
+        let jacket = graph.add_node("jacket");
+        let belt = graph.add_node("belt");
+        graph.add_edge(shirt, tie, 1);
+        graph.add_edge(tie, jacket, 1);
+        graph.add_edge(belt, jacket, 1);
+        graph.add_edge(shirt, belt, 1);
+
+        let order = graph.topological_order().unwrap();
+        let position = |n: NodeId| order.iter().position(|&o| o == n).unwrap();
+        for (_, edge) in graph.edges() {
+            assert!(position(edge.from) < position(edge.to));
+        }
+        assert!(graph.find_cycle().is_none());
+
+        let back = graph.add_edge(jacket, shirt, 1);
+        let cycle = graph.topological_order().unwrap_err();
+        assert_eq!(cycle.nodes, vec![shirt, tie, jacket]);
+        assert_eq!(cycle.edges.len(), 3);
+        assert_eq!(cycle.edges[2], back);
+
+        // a path is a tree, closing it up makes a cycle
+        let mut undirected: UnGraph<&str, u32> = Graph::new();
+        let a = undirected.add_node("a");
+        let b = undirected.add_node("b");
+        let c = undirected.add_node("c");
+        let d = undirected.add_node("d");
+        undirected.add_edge(a, b, 1);
+        undirected.add_edge(b, c, 1);
+        undirected.add_edge(c, d, 1);
+        assert!(undirected.find_cycle().is_none());
+        let closing = undirected.add_edge(d, b, 1);
+        let cycle = undirected.find_cycle().unwrap();
+        assert_eq!(cycle.nodes, vec![b, c, d]);
+        assert_eq!(cycle.edges.last(), Some(&closing));
+        for (i, &edge) in cycle.edges.iter().enumerate() {
+            let next = cycle.nodes[(i + 1) % cycle.nodes.len()];
+            assert_eq!(undirected.find_edge(cycle.nodes[i], next), Some(edge));
+        }
+    }
+
+    #[test]
+    fn bridges_test() {
+        // two triangles joined by the corridor c - d, with a dead end hanging off e
+        let mut graph: Graph<&str, u32> = Graph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        let e = graph.add_node("e");
+        let f = graph.add_node("f");
+        let g = graph.add_node("g");
+        graph.add_edge(a, b, 1);
+        graph.add_edge(b, c, 1);
+        graph.add_edge(c, a, 1);
+        let cd = graph.add_edge(c, d, 1);
+        graph.add_edge(d, e, 1);
+        graph.add_edge(e, f, 1);
+        graph.add_edge(f, d, 1);
+        let eg = graph.add_edge(e, g, 1);
+
+        let mut bridges = graph.bridges();
+        bridges.sort();
+        assert_eq!(bridges, vec![cd, eg]);
+        assert_eq!(graph.articulation_points(), vec![c, d, e]);
+
+        // a single triangle has neither
+        let mut triangle: Graph<&str, u32> = Graph::new();
+        let a = triangle.add_node("a");
+        let b = triangle.add_node("b");
+        let c = triangle.add_node("c");
+        triangle.add_edge(a, b, 1);
+        triangle.add_edge(b, c, 1);
+        triangle.add_edge(c, a, 1);
+        assert!(triangle.bridges().is_empty());
+        assert!(triangle.articulation_points().is_empty());
+    }
+
+    // greyscale maze image, '.' is a corridor and anything else a wall
+    fn maze_image(rows: &[&str]) -> Vec<u8> {
+        rows.iter()
+            .flat_map(|row| row.chars())
+            .map(|c| if c == '.' { 255 } else { 0 })
+            .collect()
+    }
+
+    #[test]
+    fn chokepoints_test() {
+        use super::opt_maze::OptMaze;
+
+        // the solution runs round the short right side of the loop, past the dead end at the right edge
+        // then through the junction between the two dead ends at the bottom
+        let rows = [
+            "#####.###",
+            "#.......#",
+            "#.#####..",
+            "#.......#",
+            "#####.###",
+            "###.....#",
+            "#####.###",
+            "#####.###",
+            "#####.###",
+        ];
+        // OptMaze indexes pixels by height, so the image has to be square
+        let maze = OptMaze::from(&maze_image(&rows), 9, 9, true);
+        let graph = maze.graph();
+        let at = |x, y| graph.find_node_xy(x, y).unwrap();
+        let chokepoints = maze.chokepoints();
+
+        let mut junctions = chokepoints.junctions.clone();
+        junctions.sort();
+        let mut expected = vec![at(5, 1), at(5, 3), at(5, 5)];
+        expected.sort();
+        assert_eq!(junctions, expected);
+        // the loop gets around the dead end's junction
+        assert!(maze.solve().unwrap().nodes.contains(&at(7, 2)));
+        assert!(graph.articulation_points().contains(&at(7, 2)));
+
+        let mut corridors: Vec<(NodeId, NodeId)> = chokepoints
+            .corridors
+            .iter()
+            .map(|&id| (graph[id].from, graph[id].to))
+            .collect();
+        corridors.sort();
+        let mut expected = vec![
+            (maze.start(), at(5, 1)),
+            (at(5, 3), at(5, 5)),
+            (at(5, 5), maze.end().unwrap()),
+        ];
+        expected.sort();
+        assert_eq!(corridors, expected);
+
+        let mut image = vec![0u8; 9 * 9 * 3];
+        maze.paint_nodes(&mut image, &chokepoints.junctions, [255, 0, 0]);
+        let coord = graph[at(5, 3)].vec_coord;
+        assert_eq!(&image[coord * 3..coord * 3 + 3], &[255, 0, 0]);
+    }
+
+    #[test]
+    fn max_flow_test() {
+        let mut graph: DiGraph<&str, u32> = Graph::new_directed();
+        let s = graph.add_node("s");
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let t = graph.add_node("t");
+        let sa = graph.add_edge(s, a, 10);
+        let sb = graph.add_edge(s, b, 5);
+        let ab = graph.add_edge(a, b, 15);
+        let at = graph.add_edge(a, t, 4);
+        let bt = graph.add_edge(b, t, 10);
+
+        let flow = graph.max_flow(s, t).unwrap();
+        assert_eq!(flow.value, 14);
+        let mut cut = flow.min_cut.clone();
+        cut.sort();
+        assert_eq!(cut, vec![at, bt]);
+        let cut_capacity: u32 = cut.iter().map(|&e| graph[e].weight).sum();
+        assert_eq!(cut_capacity, flow.value);
+        assert_eq!(flow.source_side(), vec![s, a, b]);
+        assert_eq!(flow.sink_side(), vec![t]);
+        assert_eq!(flow.flow(at).amount, 4);
+        assert_eq!(flow.flow(bt).amount, 10);
+        assert_eq!(flow.flow(sa).amount, flow.flow(ab).amount + 4);
+        assert_eq!(flow.flow(sa).amount + flow.flow(sb).amount, 14);
+        assert!(graph.max_flow(s, s).is_none());
+
+        // two routes around a square, one more straight through the middle
+        let mut undirected: Graph<&str, u32> = Graph::new();
+        let s = undirected.add_node("s");
+        let a = undirected.add_node("a");
+        let b = undirected.add_node("b");
+        let t = undirected.add_node("t");
+        undirected.add_edge(s, a, 1);
+        undirected.add_edge(a, t, 1);
+        undirected.add_edge(s, b, 1);
+        undirected.add_edge(b, t, 1);
+        assert_eq!(undirected.edge_disjoint_paths(s, t), 2);
+        let middle = undirected.add_edge(t, s, 9);
+        assert_eq!(undirected.edge_disjoint_paths(s, t), 3);
+        let flow = undirected.max_flow(s, t).unwrap();
+        assert_eq!(flow.value, 11);
+        assert_eq!(flow.flow(middle).from, s);
+        assert_eq!(flow.flow(middle).amount, 9);
+    }
+
+    // every edge must be listed under its endpoints and nowhere else
+    fn assert_consistent<N, E, Ty: EdgeType>(graph: &Graph<N, E, Ty>) {
+        let mut listed = 0;
+        for node in graph.node_ids() {
+            for edge in graph.edges_from(node) {
+                let stored = &graph[edge.id];
+                assert!(stored.from == node || (!graph.is_directed() && stored.to == node));
+                listed += 1;
+            }
+            for edge in graph.edges_to(node) {
+                let stored = &graph[edge.id];
+                assert!(stored.to == node || (!graph.is_directed() && stored.from == node));
+            }
+        }
+        let loops = graph.edges().filter(|(_, e)| e.from == e.to).count();
+        if graph.is_directed() {
+            assert_eq!(listed, graph.edge_count());
+        } else {
+            assert_eq!(listed, graph.edge_count() * 2 - loops);
+        }
+    }
+
+    #[test]
+    fn removal_test() {
+        let mut graph: Graph<&str, u32> = Graph::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        let d = graph.add_node("d");
+        graph.add_edge(a, b, 1);
+        let ac = graph.add_edge(a, c, 2);
+        graph.add_edge(b, d, 3);
+        graph.add_edge(c, d, 4);
+        graph.add_edge(d, d, 5);
+
+        assert_eq!(graph.remove_edge(ac).unwrap().weight, 2);
+        assert!(graph.find_edge(a, c).is_none());
+        assert_eq!(graph[graph.find_edge(d, d).unwrap()].weight, 5);
+        assert_consistent(&graph);
+
+        // d is the last node so c is untouched, then c gets d's old slot
+        assert_eq!(graph.remove_node(b), Some("b"));
+        assert_eq!(graph.node_count(), 3);
+        let d = NodeId::new(1);
+        assert_eq!(graph[d], "d");
+        assert_eq!(graph.neighbors(d).collect::<Vec<_>>(), vec![c, d]);
+        assert!(graph.neighbors(a).next().is_none());
+        assert_consistent(&graph);
+
+        graph.retain_nodes(|_, name| *name != "d");
+        assert_eq!(graph.node_count(), 2);
+        assert_eq!(graph.edge_count(), 0);
+        assert!(graph.remove_node(NodeId::new(2)).is_none());
+
+        let mut directed: DiGraph<u32, u32> = Graph::new_directed();
+        for i in 0..5 {
+            directed.add_node(i);
+        }
+        for i in 0..5 {
+            directed.add_edge_by_index(i, (i + 1) % 5, i as u32);
+            directed.add_edge_by_index(i, (i + 2) % 5, 10 + i as u32);
+        }
+        directed.retain_nodes(|_, n| n % 2 == 0);
+        assert_eq!(directed.node_count(), 3);
+        assert_consistent(&directed);
+        // 0 -> 2, 2 -> 4 and 4 -> 0 survive
+        assert_eq!(directed.edge_count(), 3);
+        for (_, edge) in directed.edges() {
+            let (from, to) = (directed[edge.from], directed[edge.to]);
+            if to == (from + 1) % 5 {
+                assert_eq!(edge.weight, from);
+            } else {
+                assert_eq!(to, (from + 2) % 5);
+                assert_eq!(edge.weight, 10 + from);
+            }
+        }
+        directed.retain_edges(|_, edge| edge.weight != 10);
+        assert_eq!(directed.edge_count(), 2);
+        assert_consistent(&directed);
+    }
+
+    #[test]
+    fn spatial_index_test() {
+        let mut graph: Graph<Node, u32> = Graph::new();
+        graph.build_spatial_index();
+        let mut ids = Vec::new();
+        for y in 0..10 {
+            for x in 0..10 {
+                ids.push(graph.add_node(Node::from(x, y, (y * 10 + x) as usize)));
+            }
+        }
+        assert_eq!(graph.find_node_xy(3, 7), Some(ids[73]));
+        assert_eq!(graph.find_node_xy(10, 0), None);
+
+        let mut small = graph.nodes_in_rect(2..=3, 4..=5);
+        small.sort();
+        assert_eq!(small, vec![ids[42], ids[43], ids[52], ids[53]]);
+        assert_eq!(graph.nodes_in_rect(0..=20, 0..=20).len(), 100);
+
+        // removing moves the last node into the hole, the index has to follow it
+        graph.remove_node(ids[0]);
+        assert_eq!(graph.find_node_xy(0, 0), None);
+        assert_eq!(graph.find_node_xy(9, 9), Some(ids[0]));
+        assert_eq!(graph.nodes_in_rect(9..=9, 9..=9), vec![ids[0]]);
+
+        // nodes moved in place are found at their new spot and not at the old one
+        graph[ids[73]].x = 12;
+        assert_eq!(graph.find_node_xy(12, 7), Some(ids[73]));
+        assert_eq!(graph.find_node_xy(3, 7), None);
+        assert_eq!(graph.nodes_in_rect(10..=12, 7..=7), vec![ids[73]]);
+        graph.node_mut(ids[42]).unwrap().y = 20;
+        let added = graph.add_node(Node::from(2, 4, 0));
+        assert_eq!(graph.find_node_xy(2, 4), Some(added));
+        assert_eq!(graph.find_node_xy(2, 20), Some(ids[42]));
+        assert_eq!(graph.find_node_xy(12, 7), Some(ids[73]));
+        graph.remove_node(added);
+        graph.node_mut(ids[42]).unwrap().y = 4;
+        graph[ids[73]].x = 3;
+        assert_eq!(graph.find_node_xy(2, 4), Some(ids[42]));
+        assert_eq!(graph.find_node_xy(3, 7), Some(ids[73]));
+        assert_eq!(graph.find_node_xy(12, 7), None);
+
+        // same answers without the index
+        graph.drop_spatial_index();
+        assert_eq!(graph.find_node_xy(9, 9), Some(ids[0]));
+        assert_eq!(graph.find_node_xy(3, 7), Some(ids[73]));
+        let mut scanned = graph.nodes_in_rect(2..=3, 4..=5);
+        scanned.sort();
+        assert_eq!(scanned, small);
+
+        // a table smaller than the graph still finds the nodes that fall outside it
+        graph.build_grid_index(5, 5);
+        assert_eq!(graph.find_node_xy(9, 9), Some(ids[0]));
+        assert_eq!(graph.find_node_xy(3, 3), Some(ids[33]));
+        let far = graph.add_node(Node::from(50, 50, 0));
+        assert_eq!(graph.find_node_xy(50, 50), Some(far));
+        graph.remove_node(ids[33]);
+        assert_eq!(graph.find_node_xy(3, 3), None);
+        assert_eq!(graph.find_node_xy(50, 50), Some(ids[33]));
+    }
+
+    #[test]
+    fn dot_test() {
+        use super::opt_maze::OptMaze;
+
+        let mut graph: Graph<Node, u32> = Graph::new();
+        let a = graph.add_node(Node::from(0, 0, 0));
+        let b = graph.add_node(Node::from(4, 2, 0));
+        graph.add_edge(a, b, 6);
+        assert_eq!(
+            graph.to_dot(),
+            "graph {\n    n0 [label=\"0\", pos=\"0,0\"];\n    n1 [label=\"1\", pos=\"4,-2\"];\n    n0 -- n1 [label=\"6\"];\n}\n"
+        );
+
+        let mut named: DiGraph<&str, u32> = Graph::new_directed();
+        let a = named.add_node("say \"hi\"");
+        named.add_edge(a, a, 1);
+        let mut out = Vec::new();
+        named
+            .write_dot_with(
+                &mut out,
+                &[("shape", String::from("box"))],
+                |_, name| vec![("label", name.to_string())],
+                |_, _| Vec::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "digraph {\n    node [shape=\"box\"];\n    n0 [label=\"say \\\"hi\\\"\"];\n    n0 -> n0;\n}\n"
+        );
+
+        // a cross, the start at the top and the exit at the bottom
+        let maze = OptMaze::from(&maze_image(&["#.#", "...", "#.#"]), 3, 3, true);
+        // 36 points a pixel is half an inch wide, and rows count up from the bottom
+        assert_eq!(
+            maze.to_dot(36.0),
+            "graph {\n    node [shape=\"point\", width=\"0.5\"];\n    n0 [pos=\"36,72\", color=\"green\"];\n    n1 [pos=\"36,36\", color=\"blue\"];\n    n2 [pos=\"0,36\", color=\"blue\"];\n    n3 [pos=\"72,36\", color=\"blue\"];\n    n4 [pos=\"36,0\", color=\"red\"];\n    n0 -- n1 [label=\"1\"];\n    n1 -- n2 [label=\"1\"];\n    n1 -- n3 [label=\"1\"];\n    n1 -- n4 [label=\"1\"];\n}\n"
+        );
+        assert!(maze
+            .to_dot(72.0)
+            .contains("node [shape=\"point\", width=\"1\"];"));
+    }
+
+    #[test]
+    fn graphml_test() {
+        let mut graph: Graph<Node, u32> = Graph::new();
+        let a = graph.add_node(Node::from(1, 2, 21));
+        let b = graph.add_node(Node::from(5, 2, 25));
+        graph.add_edge(a, b, 4);
+        let text = graph.to_graphml().unwrap();
+        let read: Graph<Node, u32> = Graph::from_graphml(&text).unwrap();
+        assert_eq!(read[a], graph[a]);
+        assert_eq!(read[b], graph[b]);
+        assert_eq!(read[read.find_edge(b, a).unwrap()].weight, 4);
+
+        // coordinates past i32::MAX are fine, a weight past i64::MAX has no GraphML type
+        let mut wide: Graph<Node, u64> = Graph::new();
+        let far = wide.add_node(Node::from(3_000_000_000, 1, 0));
+        let near = wide.add_node(Node::from(0, 1, 0));
+        let edge = wide.add_edge(far, near, u64::MAX);
+        assert!(wide
+            .to_graphml()
+            .unwrap_err()
+            .to_string()
+            .contains("weight"));
+        *wide.edge_weight_mut(edge).unwrap() = i64::MAX as u64;
+        let read: Graph<Node, u64> = Graph::from_graphml(&wide.to_graphml().unwrap()).unwrap();
+        assert_eq!(read[far].x, 3_000_000_000);
+        assert_eq!(read[edge].weight, i64::MAX as u64);
+
+        // the colour isn't part of Node, it should survive a round trip anyway
+        let text = r#"<?xml version="1.0"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="d0" for="node" attr.name="x" attr.type="int"/>
+  <key id="d1" for="node" attr.name="y" attr.type="int"/>
+  <key id="d2" for="node" attr.name="vec_coord" attr.type="long"/>
+  <key id="d3" for="node" attr.name="colour" attr.type="string">
+    <default>blue</default>
+  </key>
+  <key id="d4" for="edge" attr.name="weight" attr.type="long"/>
+  <graph edgedefault="directed">
+    <node id="start"><data key="d0">0</data><data key="d1">0</data><data key="d2">0</data></node>
+    <node id="exit">
+      <data key="d0">3</data><data key="d1">0</data><data key="d2">3</data>
+      <data key="d3">red</data>
+    </node>
+    <edge source="exit" target="start"><data key="d4">3</data></edge>
+  </graph>
+</graphml>"#;
+        let document: GraphMl<Node, u32, Directed> = GraphMl::from_graphml(text).unwrap();
+        assert_eq!(document.node_names, vec!["start", "exit"]);
+        let exit = NodeId::new(1);
+        assert_eq!(document.graph[exit].x, 3);
+        assert_eq!(
+            document.node_extras[0]["colour"],
+            AttrValue::String(String::from("blue"))
+        );
+        assert_eq!(document.node_extras[1]["colour"].as_str(), Some("red"));
+        assert_eq!(
+            document.graph.neighbors(exit).collect::<Vec<_>>(),
+            vec![NodeId::new(0)]
+        );
+
+        let again: GraphMl<Node, u32, Directed> =
+            GraphMl::from_graphml(&document.to_graphml().unwrap()).unwrap();
+        assert_eq!(again.node_names, document.node_names);
+        assert_eq!(again.node_extras, document.node_extras);
+        assert_eq!(again.graph[exit], document.graph[exit]);
+
+        let broken = text.replace("<data key=\"d4\">3</data>", "<data key=\"d4\">three</data>");
+        let error = GraphMl::<Node, u32, Directed>::from_graphml(&broken).unwrap_err();
+        assert_eq!(error.line, 16);
+        let missing = text.replace("<data key=\"d0\">3</data>", "");
+        let error = Graph::<Node, u32>::from_graphml(&missing).unwrap_err();
+        assert_eq!(error.line, 12);
+        assert!(error.message.contains("missing attribute x"));
+    }
+
+    #[test]
+    fn text_loader_test() {
+        // the same graph graph_test builds by hand
+        let edges = "
+            # named nodes, one edge per line
+            a b 20
+            a c 2
+            b c 5
+            c a 5   # overwrites a c
+            c d 5
+            d d 5
+        ";
+        let graph: Graph<String, u32> = Graph::from_edge_list(edges).unwrap();
+        let c = graph.find_node(|name| name == "c").unwrap();
+        assert_eq!(names(&graph, graph.bft(c).unwrap()), "c a b d ");
+        assert_eq!(graph.edge_count(), 5);
+        let a = graph.find_node(|name| name == "a").unwrap();
+        assert_eq!(graph[graph.find_edge(a, c).unwrap()].weight, 5);
+
+        let adjacency = "
+            a: b=20 c=5
+            b: c=5
+            c: d=5
+            d: d=5
+            e:          # on its own
+        ";
+        let same: Graph<String, u32> = Graph::from_adjacency_list(adjacency).unwrap();
+        assert_eq!(same.node_count(), 5);
+        assert_eq!(same.edge_count(), 5);
+        let c = same.find_node(|name| name == "c").unwrap();
+        assert_eq!(names(&same, same.bft(c).unwrap()), "c a b d ");
+
+        let directed: DiGraph<String, u32> = Graph::from_adjacency_list("a: b\nb:").unwrap();
+        assert_eq!(directed.edge_count(), 1);
+        assert_eq!(directed[EdgeId::new(0)].weight, 0);
+
+        let error = Graph::<String, u32>::from_edge_list("a b 1\n\na b x").unwrap_err();
+        assert_eq!(error.line, 3);
+        let error = Graph::<String, u32>::from_edge_list("a b 1 2").unwrap_err();
+        assert_eq!(error.line, 1);
+        let error = Graph::<String, u32>::from_adjacency_list("a: b\nc d").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2: expected `node: neighbor[=weight] ...`"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        use super::maze::Maze;
+        use super::opt_maze::OptMaze;
+
+        let mut graph: DiGraph<String, u32> =
+            Graph::from_edge_list("a b 4\nb c 2\nc a 1\na d 7").unwrap();
+        let b = graph.find_node(|name| name == "b").unwrap();
+        graph.remove_node(b);
+        let json = serde_json::to_string(&graph).unwrap();
+        let loaded: DiGraph<String, u32> = serde_json::from_str(&json).unwrap();
+        assert_consistent(&loaded);
+        assert_eq!(loaded.node_count(), graph.node_count());
+        for id in graph.edge_ids() {
+            assert_eq!(loaded[id].from, graph[id].from);
+            assert_eq!(loaded[id].to, graph[id].to);
+            assert_eq!(loaded[id].weight, graph[id].weight);
+        }
+        assert!(serde_json::from_str::<UnGraph<String, u32>>(&json).is_err());
+        let dangling = r#"{"directed":false,"nodes":["a"],"edges":[{"weight":1,"from":0,"to":3}]}"#;
+        assert!(serde_json::from_str::<UnGraph<String, u32>>(dangling).is_err());
+
+        // one edge per pair, both directions are the same pair when undirected
+        let both_ways = r#"{"directed":false,"nodes":["a","b"],"edges":[{"weight":1,"from":0,"to":1},{"weight":2,"from":1,"to":0}]}"#;
+        assert!(serde_json::from_str::<UnGraph<String, u32>>(both_ways).is_err());
+        let both_ways = both_ways.replace("false", "true");
+        let loaded: DiGraph<String, u32> = serde_json::from_str(&both_ways).unwrap();
+        assert_eq!(loaded.edge_count(), 2);
+        let twice = both_ways.replace(r#""from":1,"to":0"#, r#""from":0,"to":1"#);
+        assert!(serde_json::from_str::<DiGraph<String, u32>>(&twice).is_err());
+
+        // a straight corridor down the middle column
+        let mut image = vec![0u8; 25];
+        for y in 0..5 {
+            image[y * 5 + 2] = 255;
+        }
+        let maze = Maze::from(&image, 5, 5, true);
+        let loaded: Maze = serde_json::from_str(&serde_json::to_string(&maze).unwrap()).unwrap();
+        let coords =
+            |maze: &Maze| -> Vec<usize> { maze.bfs().iter().map(|c| c.vec_coord).collect() };
+        assert_eq!(coords(&loaded), coords(&maze));
+        assert_eq!(coords(&loaded), vec![2, 7, 12, 17, 22]);
+
+        let maze = OptMaze::from(&image, 5, 5, true);
+        let loaded: OptMaze = serde_json::from_str(&serde_json::to_string(&maze).unwrap()).unwrap();
+        assert_eq!(loaded.solve(), maze.solve());
+        assert_eq!(loaded.solve().unwrap().cost, 4);
+
+        // start and end have to point into the graph, and a maze needs a cell for every position
+        let mut json = serde_json::to_value(&maze).unwrap();
+        json["end"] = serde_json::json!(99);
+        assert!(serde_json::from_value::<OptMaze>(json.clone()).is_err());
+        json["end"] = serde_json::Value::Null;
+        assert!(!serde_json::from_value::<OptMaze>(json.clone())
+            .unwrap()
+            .is_solvable());
+        json["start"] = serde_json::json!(99);
+        assert!(serde_json::from_value::<OptMaze>(json).is_err());
+        let mut json = serde_json::to_value(Maze::from(&image, 5, 5, true)).unwrap();
+        json["width"] = serde_json::json!(6);
+        assert!(serde_json::from_value::<Maze>(json).is_err());
+        let json = serde_json::to_value(Maze::from(&image, 5, 5, true)).unwrap();
+        for (field, value) in [("vec_coord", 99), ("x", 4), ("y", 7)] {
+            let mut crafted = json.clone();
+            crafted["cells"][3][field] = serde_json::json!(value);
+            assert!(serde_json::from_value::<Maze>(crafted).is_err());
+        }
+    }
+
+    #[test]
+    fn csr_test() {
+        let mut graph: DiGraph<String, u32> =
+            Graph::from_edge_list("a b 4\nb c 2\nc a 1\na d 7\nd c 1\nc e 3\nx a 1").unwrap();
+        let x = graph.find_node(|name| name == "x").unwrap();
+        graph.remove_node(x);
+        let frozen = graph.clone().freeze();
+        assert!(frozen.is_directed());
+        assert_eq!(frozen.node_count(), graph.node_count());
+        assert_eq!(frozen.edge_count(), graph.edge_count());
+
+        // same ids, same edges in the same order
+        for id in graph.node_ids() {
+            assert_eq!(frozen[id], graph[id]);
+            let out = |e: EdgeRef<u32>| (e.id, e.from, e.to, *e.weight);
+            assert!(graph
+                .edges_from(id)
+                .map(out)
+                .eq(frozen.edges_from(id).map(out)));
+            assert!(graph.edges_to(id).map(out).eq(frozen.edges_to(id).map(out)));
+            assert_eq!(frozen.in_degree(id), graph.in_degree(id));
+            assert_eq!(frozen.bft(id), graph.bft(id));
+            assert_eq!(frozen.dft(id), graph.dft(id));
+        }
+        let a = frozen.find_node(|name| name == "a").unwrap();
+        let e = frozen.find_node(|name| name == "e").unwrap();
+        assert_eq!(frozen.dijkstra(a, e), graph.dijkstra(a, e));
+        assert_eq!(frozen.dijkstra(a, e).unwrap().cost, 9);
+        assert!(frozen.dijkstra(e, a).is_none());
+        assert_eq!(frozen.dijkstra_all(e).unwrap().distance(a), None);
+
+        // undirected edges can be walked from either end
+        let mut grid: Graph<Node, u32> = Graph::new();
+        let ids: Vec<NodeId> = (0..4)
+            .map(|i| grid.add_node(Node::from(i % 2, i / 2, i as usize)))
+            .collect();
+        grid.add_edge(ids[0], ids[1], 1);
+        grid.add_edge(ids[1], ids[3], 1);
+        grid.add_edge(ids[2], ids[0], 5);
+        let grid = grid.freeze();
+        assert!(!grid.is_directed());
+        assert_eq!(
+            grid.neighbors(ids[0]).collect::<Vec<_>>(),
+            vec![ids[1], ids[2]]
+        );
+        assert_eq!(grid.in_degree(ids[1]), 2);
+        let path = grid
+            .astar_with(ids[2], ids[3], Heuristic::Manhattan)
+            .unwrap();
+        assert_eq!(path.nodes, vec![ids[2], ids[0], ids[1], ids[3]]);
+        assert_eq!(path.cost, 7);
+    }
+
+    // records what the searches report, stops once `stop_at` is discovered
+    struct Recorder {
+        stop_at: Option<NodeId>,
+        events: Vec<String>,
+        depths: Vec<Option<usize>>,
+    }
+
+    impl Visitor<u32> for Recorder {
+        fn discover(&mut self, node: NodeId, via: Option<EdgeRef<'_, u32>>) -> Control {
+            self.events.push(format!("d{}", node.index()));
+            self.depths[node.index()] =
+                Some(via.map_or(0, |e| self.depths[e.from.index()].unwrap() + 1));
+            if Some(node) == self.stop_at {
+                Control::Break
+            } else {
+                Control::Continue
+            }
+        }
+
+        fn examine_edge(&mut self, edge: EdgeRef<'_, u32>) -> Control {
+            // never walk past node 3
+            if edge.from.index() == 3 {
+                Control::Prune
+            } else {
+                Control::Continue
+            }
+        }
+
+        fn finish(&mut self, node: NodeId) -> Control {
+            self.events.push(format!("f{}", node.index()));
+            Control::Continue
+        }
+    }
+
+    #[test]
+    fn traversal_test() {
+        let graph: Graph<String, u32> = Graph::from_edge_list("a b\na c\nb d\nc d\nd e").unwrap();
+        let a = NodeId::new(0);
+        let e = graph.find_node(|name| name == "e").unwrap();
+
+        // the iterators give the same order as bft and dft, and only do as much work as asked
+        assert_eq!(graph.bfs(a).collect::<Vec<_>>(), graph.bft(a).unwrap());
+        assert_eq!(graph.dfs(a).collect::<Vec<_>>(), graph.dft(a).unwrap());
+        assert_eq!(names(&graph, graph.bfs(e).take(2).collect()), "e d ");
+        assert_eq!(graph.dfs(NodeId::new(9)).count(), 0);
+        let frozen = graph.clone().freeze();
+        assert!(frozen.bfs(a).eq(graph.bfs(a)));
+
+        let recorder = || Recorder {
+            stop_at: None,
+            events: Vec::new(),
+            depths: vec![None; 5],
+        };
+        let mut visitor = recorder();
+        assert_eq!(graph.bfs_visit(a, &mut visitor), Control::Continue);
+        assert_eq!(visitor.events.join(" "), "d0 d1 d2 f0 d3 f1 f2 f3");
+        assert_eq!(
+            visitor.depths,
+            vec![Some(0), Some(1), Some(1), Some(2), None]
+        );
+
+        let mut visitor = recorder();
+        assert_eq!(graph.dfs_visit(a, &mut visitor), Control::Continue);
+        assert_eq!(visitor.events.join(" "), "d0 d1 d3 f3 f1 d2 f2 f0");
+
+        visitor = recorder();
+        visitor.stop_at = Some(NodeId::new(2));
+        assert_eq!(frozen.bfs_visit(a, &mut visitor), Control::Break);
+        assert_eq!(visitor.events.join(" "), "d0 d1 d2");
+    }
+
+    #[test]
+    fn bidirectional_test() {
+        let graph: DiGraph<String, u32> = Graph::from_edge_list(
+            "a b 1\nb c 1\nc d 1\nd e 1\na e 10\na f 2\nf e 5\ne g 1\ng a 1\nh a 1",
+        )
+        .unwrap();
+        let node = |name: &str| graph.find_node(|n| n == name).unwrap();
+        let (a, e) = (node("a"), node("e"));
+
+        // the cheapest route takes more hops than the shortest one
+        let path = graph.bidirectional_dijkstra(a, e).unwrap();
+        assert_eq!(names(&graph, path.nodes), "a b c d e ");
+        assert_eq!(path.cost, 4);
+        assert_eq!(
+            names(&graph, graph.bidirectional_bfs(a, e).unwrap()),
+            "a e "
+        );
+
+        // every pair agrees with the one sided searches, edges are only walked forwards
+        for from in graph.node_ids() {
+            let mut hop_counts = vec![None; graph.node_count()];
+            hop_counts[from.index()] = Some(0);
+            for node in graph.bfs(from) {
+                for next in graph.neighbors(node) {
+                    if hop_counts[next.index()].is_none() {
+                        hop_counts[next.index()] = hop_counts[node.index()].map(|h| h + 1);
+                    }
+                }
+            }
+            for to in graph.node_ids() {
+                let expected = graph.dijkstra(from, to);
+                let path = graph.bidirectional_dijkstra(from, to);
+                assert_eq!(
+                    path.as_ref().map(|p| p.cost),
+                    expected.as_ref().map(|p| p.cost)
+                );
+                let hops = graph
+                    .bidirectional_bfs(from, to)
+                    .map(|nodes| nodes.len() - 1);
+                assert_eq!(hops, hop_counts[to.index()]);
+            }
+        }
+        assert!(graph.bidirectional_bfs(a, node("h")).is_none());
+        assert_eq!(graph.bidirectional_dijkstra(a, a).unwrap().nodes, vec![a]);
+
+        let frozen = graph.clone().freeze();
+        assert_eq!(
+            frozen.bidirectional_dijkstra(node("h"), e),
+            graph.dijkstra(node("h"), e)
+        );
+    }
+
+    #[test]
+    fn metrics_test() {
+        // a path a - b - c - d with a shortcut hanging off b
+        let graph: Graph<String, u32> =
+            Graph::from_edge_list("a b 1\nb c 1\nc d 1\nb e 5\ne d 1").unwrap();
+        let node = |name: &str| graph.find_node(|n| n == name).unwrap();
+        assert_eq!(graph.degree_histogram(), vec![0, 1, 3, 1]);
+
+        let hops = graph.eccentricities();
+        assert_eq!(hops.of(node("a")), Some(3));
+        assert_eq!(hops.diameter(), Some(3));
+        assert_eq!(hops.radius(), Some(2));
+        assert_eq!(names(&graph, hops.center()), "b c e ");
+        assert_eq!(names(&graph, hops.periphery()), "a d ");
+
+        let weighted = graph.weighted_eccentricities();
+        assert_eq!(weighted.of(node("e")), Some(4));
+        assert_eq!(weighted.diameter(), Some(4));
+        assert_eq!(weighted.radius(), Some(2));
+        assert_eq!(names(&graph, weighted.center()), "c ");
+        assert_eq!(names(&graph, weighted.periphery()), "a e ");
+
+        // one way edges leave some nodes unable to reach the rest
+        let directed: DiGraph<String, u32> =
+            Graph::from_edge_list("a b 1\nb c 1\nc a 1\nc d 1").unwrap();
+        let hops = directed.eccentricities();
+        assert_eq!(hops.of(NodeId::new(3)), None);
+        assert_eq!(hops.diameter(), None);
+        assert!(hops.periphery().is_empty());
+        assert_eq!(hops.radius(), Some(2));
+        assert_eq!(directed.degree_histogram(), vec![0, 1, 2, 1]);
+        let looped: Graph<String, u32> = Graph::from_edge_list("a a").unwrap();
+        assert_eq!(looped.degree_histogram(), vec![0, 0, 1]);
+        let looped: DiGraph<String, u32> = Graph::from_edge_list("a a").unwrap();
+        assert_eq!(looped.degree_histogram(), vec![0, 0, 1]);
+        assert_eq!(Graph::<String, u32>::new().eccentricities().radius(), None);
+    }
+
+    #[test]
+    fn centrality_test() {
+        let tree: Graph<String, u32> = Graph::from_edge_list("a b\nb c\nc d\nb e").unwrap();
+        assert_eq!(tree.betweenness_centrality(), vec![0.0, 5.0, 3.0, 0.0, 0.0]);
+        assert_eq!(tree.degree_centrality(), vec![0.25, 0.75, 0.5, 0.25, 0.25]);
+        let closeness = tree.closeness_centrality();
+        assert!((closeness[1] - 4.0 / 5.0).abs() < 1e-9);
+        assert!(closeness[1] > closeness[2] && closeness[2] > closeness[0]);
+
+        // opposite corners of a square are joined by two routes, the weights make them unequal
+        let square: Graph<String, u32> =
+            Graph::from_edge_list("a b 1\nb c 1\nc d 5\nd a 5").unwrap();
+        assert_eq!(square.betweenness_centrality(), vec![0.5; 4]);
+        assert_eq!(
+            square.weighted_betweenness_centrality(),
+            vec![0.5, 1.0, 0.5, 0.0]
+        );
+        let closeness = square.weighted_closeness_centrality();
+        assert!((closeness[1] - 3.0 / 8.0).abs() < 1e-9);
+
+        // a walker is as likely to be on any node of a cycle
+        let cycle: DiGraph<String, u32> = Graph::from_edge_list("a b\nb c\nc a").unwrap();
+        for rank in cycle.page_rank(0.85, 100) {
+            assert!((rank - 1.0 / 3.0).abs() < 1e-9);
+        }
+        // c has no way out, a walker stuck there jumps to a random node
+        let chain: DiGraph<String, u32> = Graph::from_edge_list("a b\nb c\nd c").unwrap();
+        let ranks = chain.page_rank(0.85, 100);
+        assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(ranks[2] > ranks[1] && ranks[1] > ranks[0]);
+        assert_eq!(ranks[0], ranks[3]);
+        assert_eq!(chain.betweenness_centrality(), vec![0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(
+            chain.degree_centrality(),
+            vec![1.0 / 6.0, 2.0 / 6.0, 2.0 / 6.0, 1.0 / 6.0]
+        );
+        let both_ways: DiGraph<String, u32> = Graph::from_edge_list("a b\nb a").unwrap();
+        assert_eq!(both_ways.degree_centrality(), vec![1.0, 1.0]);
+    }
+
+    #[test]
+    fn communities_test() {
+        // two triangles held together by a single edge
+        let graph: Graph<String, u32> =
+            Graph::from_edge_list("a b 1\nb c 1\nc a 1\nd e 1\ne f 1\nf d 1\nc d 1").unwrap();
+        let louvain = graph.louvain();
+        assert_eq!(louvain.count(), 2);
+        assert_eq!(louvain.partition().labels(), &[0, 0, 0, 1, 1, 1]);
+        assert!((louvain.modularity() - (6.0 / 7.0 - 0.5)).abs() < 1e-9);
+        assert_eq!(graph.modularity(&[0; 6]), 0.0);
+
+        for seed in 0..20 {
+            let spread = graph.label_propagation(seed);
+            assert!(spread.count() <= 2, "seed {}", seed);
+            assert!(spread.modularity() >= 0.0);
+            let a = spread.community_of(NodeId::new(0));
+            assert_eq!(spread.community_of(NodeId::new(1)), a);
+            assert_eq!(spread.community_of(NodeId::new(2)), a);
+        }
+        assert_eq!(graph.label_propagation(0).partition(), louvain.partition());
+        // the same seed gives the same run
+        assert_eq!(
+            graph.label_propagation(3).partition(),
+            graph.label_propagation(3).partition()
+        );
+
+        // nodes with no edges stay on their own
+        let mut lonely: DiGraph<String, u32> = Graph::from_edge_list("a b 1\nb a 1\nc").unwrap();
+        lonely.add_node("d".to_string());
+        let communities = lonely.louvain();
+        assert_eq!(communities.count(), 3);
+        assert_eq!(lonely.label_propagation(1).count(), 3);
+
+        // the same square splits along its light edges
+        let square = |weights: [u32; 4]| -> Graph<String, u32> {
+            let list = format!(
+                "a b {}\nb c {}\nc d {}\nd a {}",
+                weights[0], weights[1], weights[2], weights[3]
+            );
+            Graph::from_edge_list(&list).unwrap()
+        };
+        let across = square([10, 1, 10, 1]).louvain();
+        assert_eq!(across.partition().labels(), &[0, 0, 1, 1]);
+        assert!((across.modularity() - (20.0 / 22.0 - 0.5)).abs() < 1e-9);
+        let around = square([1, 10, 1, 10]).louvain();
+        assert_eq!(around.partition().labels(), &[0, 1, 1, 0]);
+        // edges that weigh nothing leave nothing to group by
+        assert_eq!(square([0; 4]).louvain().count(), 4);
+        assert_eq!(square([0; 4]).modularity(&[0, 0, 1, 1]), 0.0);
+    }
+
+    fn assert_proper<N, E, Ty: EdgeType>(graph: &Graph<N, E, Ty>, coloring: &Coloring) {
+        for (_, edge) in graph.edges() {
+            if edge.from != edge.to {
+                assert_ne!(coloring.color_of(edge.from), coloring.color_of(edge.to));
+            }
+        }
+        let used = coloring.colors().iter().max().map_or(0, |&c| c + 1);
+        assert_eq!(coloring.count(), used);
+    }
+
+    #[test]
+    fn coloring_test() {
+        // crown graph, a_i and b_j are linked unless i == j, nodes go a0 b0 a1 b1 ...
+        let mut crown: Graph<usize, u32> = Graph::new();
+        let ids: Vec<NodeId> = (0..8).map(|i| crown.add_node(i)).collect();
+        for i in 0..4 {
+            for j in 0..4 {
+                if i < j {
+                    crown.add_edge(ids[2 * i], ids[2 * j + 1], 1);
+                    crown.add_edge(ids[2 * j], ids[2 * i + 1], 1);
+                }
+            }
+        }
+        // taking the nodes by index is the worst case, the graph only needs two colors
+        let greedy = crown.greedy_coloring(ColoringOrder::NodeOrder);
+        assert_proper(&crown, &greedy);
+        assert_eq!(greedy.count(), 4);
+        let dsatur = crown.dsatur_coloring();
+        assert_proper(&crown, &dsatur);
+        assert_eq!(dsatur.count(), 2);
+        let sides = ColoringOrder::Custom(vec![ids[0], ids[2], ids[4], ids[6]]);
+        let custom = crown.greedy_coloring(sides);
+        assert_eq!(custom.count(), 2);
+        assert_eq!(custom.nodes_with(0), vec![ids[0], ids[2], ids[4], ids[6]]);
+        for order in [ColoringOrder::LargestFirst, ColoringOrder::SmallestLast] {
+            assert_proper(&crown, &crown.greedy_coloring(order));
+        }
+
+        // an odd cycle needs three, direction and self loops don't matter
+        let cycle: DiGraph<String, u32> =
+            Graph::from_edge_list("a b\nb c\nc d\nd e\ne a\na a").unwrap();
+        for coloring in [
+            cycle.dsatur_coloring(),
+            cycle.greedy_coloring(ColoringOrder::SmallestLast),
+        ] {
+            assert_proper(&cycle, &coloring);
+            assert_eq!(coloring.count(), 3);
+        }
+        assert_eq!(Graph::<String, u32>::new().dsatur_coloring().count(), 0);
+    }
+
+    fn assert_walks_every_edge<Ty: EdgeType>(graph: &Graph<String, u32, Ty>, trail: &Trail) {
+        assert_eq!(trail.edges.len(), graph.edge_count());
+        assert_eq!(trail.nodes.len(), trail.edges.len() + 1);
+        let mut used = vec![false; graph.edge_count()];
+        for (i, &id) in trail.edges.iter().enumerate() {
+            assert!(!used[id.index()]);
+            used[id.index()] = true;
+            let edge = graph
+                .edges_from(trail.nodes[i])
+                .find(|e| e.id == id)
+                .unwrap();
+            assert_eq!(edge.to, trail.nodes[i + 1]);
+        }
+    }
+
+    #[test]
+    fn euler_test() {
+        // a square with one diagonal, the two ends of the diagonal have odd degree
+        let envelope: Graph<String, u32> =
+            Graph::from_edge_list("a b\nb c\nc d\nd a\na c").unwrap();
+        assert!(envelope.has_eulerian_path());
+        assert!(!envelope.has_eulerian_circuit());
+        assert!(envelope.eulerian_circuit().is_none());
+        let trail = envelope.eulerian_path().unwrap();
+        assert_walks_every_edge(&envelope, &trail);
+        assert!(!trail.is_closed());
+        assert_eq!(
+            names(&envelope, vec![trail.nodes[0], trail.nodes[5]]),
+            "a c "
+        );
+
+        // a detour from a to c over a new node evens out the degrees, self loops always do
+        let mut looped = envelope.clone();
+        let (a, c, d) = (NodeId::new(0), NodeId::new(2), NodeId::new(3));
+        let e = looped.add_node("e".to_string());
+        looped.add_edge(a, e, 0);
+        looped.add_edge(e, c, 0);
+        looped.add_edge(d, d, 0);
+        let circuit = looped.eulerian_circuit().unwrap();
+        assert_walks_every_edge(&looped, &circuit);
+        assert!(circuit.is_closed());
+
+        // one way streets, c has one more way out than in and d one more way in
+        let directed: DiGraph<String, u32> = Graph::from_edge_list("a b\nb c\nc a\nc d").unwrap();
+        let trail = directed.eulerian_path().unwrap();
+        assert_walks_every_edge(&directed, &trail);
+        assert_eq!(
+            names(&directed, vec![trail.nodes[0], trail.nodes[4]]),
+            "c d "
+        );
+        assert!(!directed.has_eulerian_circuit());
+        let two_out: DiGraph<String, u32> = Graph::from_edge_list("a b\na c").unwrap();
+        assert!(!two_out.has_eulerian_path());
+
+        // edges that can't reach each other, isolated nodes don't matter
+        let split: Graph<String, u32> =
+            Graph::from_edge_list("a b\nb c\nc a\nd e\ne f\nf d").unwrap();
+        assert!(!split.has_eulerian_path());
+        let lonely: Graph<String, u32> = Graph::from_edge_list("a b\nb c\nc a\nd").unwrap();
+        assert!(lonely.has_eulerian_circuit());
+        assert!(Graph::<String, u32>::new().eulerian_path().is_none());
+    }
+
+    #[test]
+    fn k_shortest_paths_test() {
+        let graph: DiGraph<String, u32> =
+            Graph::from_edge_list("c d 3\nc e 2\nd f 4\ne d 1\ne f 2\ne g 3\nf g 2\nf h 1\ng h 2")
+                .unwrap();
+        let node = |name: &str| graph.find_node(|n| n == name).unwrap();
+        let (c, h) = (node("c"), node("h"));
+
+        let paths = graph.k_shortest_paths(c, h, 3);
+        assert_eq!(paths[0], graph.dijkstra(c, h).unwrap());
+        let found: Vec<(String, u32)> = paths
+            .iter()
+            .map(|p| (names(&graph, p.nodes.clone()), p.cost))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("c e f h ".to_string(), 5),
+                ("c e g h ".to_string(), 7),
+                ("c d f h ".to_string(), 8),
+            ]
+        );
+
+        // asking for more than there are gives every simple path once
+        let all = graph.k_shortest_paths(c, h, 10);
+        let costs: Vec<u32> = all.iter().map(|p| p.cost).collect();
+        assert_eq!(costs, vec![5, 7, 8, 8, 8, 11, 11]);
+        for path in &all {
+            let mut nodes = path.nodes.clone();
+            nodes.sort();
+            nodes.dedup();
+            assert_eq!(nodes.len(), path.nodes.len());
+        }
+        assert!(graph.k_shortest_paths(h, c, 3).is_empty());
+        assert!(graph.k_shortest_paths(c, h, 0).is_empty());
+        assert_eq!(graph.clone().freeze().k_shortest_paths(c, h, 10), all);
+
+        // both ways round a square
+        let square: Graph<String, u32> =
+            Graph::from_edge_list("a b 1\nb c 1\nc d 1\nd a 2").unwrap();
+        let paths = square.k_shortest_paths(NodeId::new(0), NodeId::new(2), 5);
+        assert_eq!(paths.len(), 2);
+        assert_eq!((paths[0].cost, paths[1].cost), (2, 3));
+    }
+
+    #[test]
+    fn k_shortest_braided_test() {
+        use super::opt_maze::OptMaze;
+
+        // corridors along every odd row and column, a maze with loops everywhere and a long way through
+        // spur searches that flood the graph crawl here, the ones steered at the exit stop almost at once
+        let size = 801;
+        let image: Vec<u8> = (0..size * size)
+            .map(|i| {
+                let (x, y) = (i % size, i / size);
+                let inside = x > 0 && y > 0 && x < size - 1 && y < size - 1;
+                let open = inside && (x % 2 == 1 || y % 2 == 1);
+                let door = (x, y) == (1, 0) || (x, y) == (size - 2, size - 1);
+                if open || door {
+                    255
+                } else {
+                    0
+                }
+            })
+            .collect();
+        let maze = OptMaze::from(&image, size, size, true);
+        assert!(maze.graph().node_count() > 150_000);
+
+        let shortest = maze.solve().unwrap();
+        assert!(shortest.hops() > 10_000);
+        let paths = maze.solve_k_shortest(3);
+        assert_eq!(paths.len(), 3);
+        assert_eq!(paths[0], shortest);
+        // plenty of loops means plenty of routes just as short
+        assert!(paths.iter().all(|p| p.cost == shortest.cost));
+        assert_ne!(paths[0].nodes, paths[1].nodes);
+        assert_ne!(paths[1].nodes, paths[2].nodes);
+        assert_ne!(paths[0].nodes, paths[2].nodes);
     }
 }
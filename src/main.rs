@@ -3,19 +3,16 @@ use lib::maze::Maze;
 use lib::opt_maze::OptMaze;
 use std::{env, process::exit};
 
-use std::collections::VecDeque;
 use std::fs::File;
-use std::io::BufWriter;
-use std::path::Path;
 use std::time::Instant;
 
 //
-fn test_maze_generation_speed(buf: &Vec<u8>, width: u32, height: u32, is_greyscale: bool) {
+fn test_maze_generation_speed(buf: &[u8], width: u32, height: u32, is_greyscale: bool) {
     let mut time_naive: u128 = 0;
     let runs: u128 = 1000;
-    for i in 0..runs {
+    for _ in 0..runs {
         let time = Instant::now();
-        let mut naive_maze = Maze::from(&buf, width, height, is_greyscale);
+        let _naive_maze = Maze::from(buf, width, height, is_greyscale);
         time_naive += time.elapsed().as_nanos();
     }
     println!(
@@ -25,9 +22,9 @@ fn test_maze_generation_speed(buf: &Vec<u8>, width: u32, height: u32, is_greysca
     );
 
     let mut time_opt: u128 = 0;
-    for i in 0..runs {
+    for _ in 0..runs {
         let time = Instant::now();
-        let mut opt_maze = OptMaze::from(&buf, width, height, is_greyscale);
+        let _opt_maze = OptMaze::from(buf, width, height, is_greyscale);
         time_opt += time.elapsed().as_nanos();
     }
     println!(
@@ -51,13 +48,13 @@ fn main() {
     }
 
     let img_file = &args[1];
-    let mut out = String::clone(&img_file);
+    let mut out = String::clone(img_file);
 
     if !img_file.contains(".png") {
         println!("input file must be a png");
         exit(1);
     }
-    let output_file = if args.len() == 2 {
+    let _output_file = if args.len() == 2 {
         let png_location = out
             .find(".png")
             .expect("Couldn't find .png?? how did i get here");
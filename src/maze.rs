@@ -1,12 +1,12 @@
 use std::collections::VecDeque;
 use std::rc::Rc;
-use std::usize;
 
 pub type CellRef = Rc<Cell>;
 
 // cell is a location in maze, has x, y coordinate
 // 0, 0 at top left
 #[derive(Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cell {
     is_wall: bool,
     x: u32,
@@ -46,9 +46,69 @@ pub struct Maze {
     end: CellRef,
 }
 
+// start and end are stored as indices into cells, they share the cells' Rc when loaded back
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct StoredMaze<C> {
+    width: u32,
+    height: u32,
+    cells: Vec<C>,
+    start: usize,
+    end: usize,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Maze {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StoredMaze {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(|cell| cell.as_ref()).collect(),
+            start: self.start.vec_coord,
+            end: self.end.vec_coord,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Maze {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        let stored = StoredMaze::<Cell>::deserialize(deserializer)?;
+        // bfs finds cells by position, so every position needs one
+        if stored.cells.len() as u64 != u64::from(stored.width) * u64::from(stored.height) {
+            return Err(D::Error::custom(
+                "maze cells don't match its width and height",
+            ));
+        }
+        if stored.start >= stored.cells.len() || stored.end >= stored.cells.len() {
+            return Err(D::Error::custom("maze start or end is outside its cells"));
+        }
+        // and the cell at each index has to be the one for that position, bfs indexes by them
+        let width = stored.width as usize;
+        for (i, cell) in stored.cells.iter().enumerate() {
+            if cell.vec_coord != i || cell.x as usize != i % width || cell.y as usize != i / width {
+                return Err(D::Error::custom(format!(
+                    "maze cell {} doesn't match its position",
+                    i
+                )));
+            }
+        }
+        let cells: Vec<CellRef> = stored.cells.into_iter().map(Rc::new).collect();
+        Ok(Maze {
+            width: stored.width,
+            height: stored.height,
+            start: Rc::clone(&cells[stored.start]),
+            end: Rc::clone(&cells[stored.end]),
+            cells,
+        })
+    }
+}
+
 impl Maze {
     // create a maze from image buffer
-    pub fn from(image_buff: &Vec<u8>, width: u32, height: u32, is_greyscale: bool) -> Maze {
+    pub fn from(image_buff: &[u8], width: u32, height: u32, is_greyscale: bool) -> Maze {
         let mut cells: Vec<CellRef> = Vec::new();
         let mut start: CellRef = Rc::new(Cell::new());
         let mut end: CellRef = Rc::new(Cell::new());
@@ -107,7 +167,7 @@ impl Maze {
     }
 
     // gets all unvisited neighbors
-    fn get_neighbors(&self, cell: &CellRef, visited_vec: &Vec<bool>) -> Vec<CellRef> {
+    fn get_neighbors(&self, cell: &CellRef, visited_vec: &[bool]) -> Vec<CellRef> {
         let mut neighbors: Vec<CellRef> = Vec::new();
 
         // check cell above
@@ -169,8 +229,7 @@ impl Maze {
         visited_vec[self.start.vec_coord] = true;
         queue.push_back(Rc::clone(&self.start));
 
-        while queue.len() > 0 {
-            let current_cell = queue.pop_front().unwrap();
+        while let Some(current_cell) = queue.pop_front() {
             for cell in self.get_neighbors(&current_cell, &visited_vec) {
                 visited_vec[cell.vec_coord] = true;
                 parent_vec[cell.vec_coord] = current_cell.vec_coord;
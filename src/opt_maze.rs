@@ -1,21 +1,72 @@
 use crate::graph::*;
-use std::collections::VecDeque;
-use std::fmt::Pointer;
 use std::fs::File;
-use std::io::BufWriter;
+use std::io::{self, BufWriter, Write};
 use std::path::Path;
 use std::process::exit;
-use std::rc::Rc;
-use std::thread::current;
 use std::time::Instant;
-use std::vec;
-type RefNode = Rc<Node>;
+
 pub struct OptMaze {
     width: u32,
     height: u32,
-    graph: Graph,
-    start: RefNode,
-    end: RefNode,
+    graph: Graph<Node, u32>,
+    start: NodeId,
+    // None when no corridor from the start reaches the bottom row
+    end: Option<NodeId>,
+}
+
+// what an OptMaze looks like on disk, the graph is borrowed when saving and owned when loading
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct StoredOptMaze<G> {
+    width: u32,
+    height: u32,
+    graph: G,
+    start: NodeId,
+    end: Option<NodeId>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for OptMaze {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StoredOptMaze {
+            width: self.width,
+            height: self.height,
+            graph: &self.graph,
+            start: self.start,
+            end: self.end,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for OptMaze {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        let stored = StoredOptMaze::<Graph<Node, u32>>::deserialize(deserializer)?;
+        let exists = |id: NodeId| stored.graph.check_if_node_exist(id);
+        if !exists(stored.start) || !stored.end.is_none_or(exists) {
+            return Err(D::Error::custom(
+                "maze start or end is not a node of its graph",
+            ));
+        }
+        Ok(OptMaze {
+            width: stored.width,
+            height: stored.height,
+            graph: stored.graph,
+            start: stored.start,
+            end: stored.end,
+        })
+    }
+}
+
+// what every route from the start to the exit passes through, paint them with paint_edges and paint_nodes
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Chokepoints {
+    // corridors, the bridges on the solution path
+    pub corridors: Vec<EdgeId>,
+    // junctions, the articulation points on it that can't be bypassed
+    pub junctions: Vec<NodeId>,
 }
 
 struct Neighbors {
@@ -29,21 +80,21 @@ fn xy_to_image_buff_location(x: u32, y: u32, height: u32) -> usize {
     ((height * y) + x) as usize
 }
 
-fn is_path(image_buff: &Vec<u8>, coord: usize, is_grey: bool) -> bool {
+fn is_path(image_buff: &[u8], coord: usize, is_grey: bool) -> bool {
     if is_grey {
-        image_buff[(coord) as usize] == 255
+        image_buff[coord] == 255
     } else {
-        image_buff[(coord * 3) as usize] == 255
-            && image_buff[(coord * 3 + 1) as usize] == 255
-            && image_buff[(coord * 3 + 2) as usize] == 255
+        image_buff[coord * 3] == 255
+            && image_buff[coord * 3 + 1] == 255
+            && image_buff[coord * 3 + 2] == 255
     }
 }
 
 fn get_neighbors(
     x: u32,
     y: u32,
-    image_buff: &Vec<u8>,
-    visited_vec: &Vec<bool>,
+    image_buff: &[u8],
+    visited_vec: &[bool],
     width: u32,
     height: u32,
     is_grey: bool,
@@ -94,17 +145,15 @@ fn get_neighbors(
     neighbors
 }
 
-fn convert_greyscale_buf_to_rgb(image_buffer: &mut Vec<u8>) -> Vec<u8> {
-    let mut color_buf: Vec<u8> = Vec::new();
+// not called while write_image leaves greyscale buffers alone, see the commented out block there
+#[allow(dead_code)]
+fn convert_greyscale_buf_to_rgb(image_buffer: &[u8]) -> Vec<u8> {
+    let mut color_buf: Vec<u8> = Vec::with_capacity(image_buffer.len() * 3);
     for cell in image_buffer {
         if *cell == 255 {
-            color_buf.push(255);
-            color_buf.push(255);
-            color_buf.push(255);
+            color_buf.extend_from_slice(&[255, 255, 255]);
         } else {
-            color_buf.push(0);
-            color_buf.push(0);
-            color_buf.push(0);
+            color_buf.extend_from_slice(&[0, 0, 0]);
         }
     }
     color_buf
@@ -113,39 +162,35 @@ fn convert_greyscale_buf_to_rgb(image_buffer: &mut Vec<u8>) -> Vec<u8> {
 impl OptMaze {
     // TODO: Need to add edges, and needs to be refactored
     // seems to be about 25%-35% slower than filling in all the squares, without edge adding
-    pub fn from(image_buff: &Vec<u8>, width: u32, height: u32, is_greyscale: bool) -> OptMaze {
+    pub fn from(image_buff: &[u8], width: u32, height: u32, is_greyscale: bool) -> OptMaze {
         let mut graph = Graph::new();
-        let mut start = Rc::new(Node::new());
-        let mut end = Rc::new(Node::new());
+        // the corridor walk below looks nodes up by position, dropped again once it is done
+        graph.build_grid_index(width, height);
+        let mut start = None;
         // Find Start
         for i in 0..width {
-            let is_not_wall = if is_greyscale {
-                image_buff[(i) as usize] == 255
-            } else {
-                image_buff[(i * 3) as usize] == 255
-                    && image_buff[(i * 3 + 1) as usize] == 255
-                    && image_buff[(i * 3 + 2) as usize] == 255
-            };
-
-            if is_not_wall {
-                start = graph.add_node(i, 0, i as usize);
+            if is_path(image_buff, i as usize, is_greyscale) {
+                start = Some(graph.add_node(Node::from(i, 0, i as usize)));
                 break;
             }
         }
-        if start.x == 0 {
-            println!("Failed to find maze start");
-            exit(0);
-        }
+        let start = match start {
+            Some(start) if graph[start].x != 0 => start,
+            _ => {
+                println!("Failed to find maze start");
+                exit(0);
+            }
+        };
 
         // gets all unvisited neighbors
 
         // breath first approach to finding nodes
-        let mut node_queue = vec![Rc::clone(&start)];
+        let mut node_queue = vec![start];
         let mut visited = vec![false; (width * height) as usize];
-        visited[start.vec_coord] = true;
+        visited[graph[start].vec_coord] = true;
 
-        while !node_queue.is_empty() {
-            let current_node = node_queue.pop().unwrap();
+        while let Some(current_id) = node_queue.pop() {
+            let current_node = graph[current_id];
             let neighbors = get_neighbors(
                 current_node.x,
                 current_node.y,
@@ -166,10 +211,13 @@ impl OptMaze {
                     let left_neighbors =
                         get_neighbors(x, y, image_buff, &visited, width, height, is_greyscale);
                     if left_neighbors.above || left_neighbors.below || !left_neighbors.left {
-                        let new_node =
-                            &graph.add_node(x, y, xy_to_image_buff_location(x, y, height));
-                        graph.add_edge(&current_node, new_node, left_idx);
-                        node_queue.push(Rc::clone(new_node));
+                        let new_node = graph.add_node(Node::from(
+                            x,
+                            y,
+                            xy_to_image_buff_location(x, y, height),
+                        ));
+                        graph.add_edge(current_id, new_node, left_idx);
+                        node_queue.push(new_node);
                         break;
                     } else {
                         left_idx += 1;
@@ -185,10 +233,13 @@ impl OptMaze {
                     let right_neighbors =
                         get_neighbors(x, y, image_buff, &visited, width, height, is_greyscale);
                     if right_neighbors.above || right_neighbors.below || !right_neighbors.right {
-                        let new_node =
-                            &graph.add_node(x, y, xy_to_image_buff_location(x, y, height));
-                        graph.add_edge(&current_node, new_node, right_idx);
-                        node_queue.push(Rc::clone(new_node));
+                        let new_node = graph.add_node(Node::from(
+                            x,
+                            y,
+                            xy_to_image_buff_location(x, y, height),
+                        ));
+                        graph.add_edge(current_id, new_node, right_idx);
+                        node_queue.push(new_node);
                         break;
                     } else {
                         right_idx += 1;
@@ -211,15 +262,18 @@ impl OptMaze {
                             match graph.find_node_xy(x, y - 1) {
                                 None => break,
                                 Some(to) => {
-                                    graph.add_edge(&current_node, &to, above_idx);
+                                    graph.add_edge(current_id, to, above_idx + 1);
                                     break;
                                 }
                             }
                         }
-                        let new_node =
-                            &graph.add_node(x, y, xy_to_image_buff_location(x, y, height));
-                        graph.add_edge(&current_node, new_node, above_idx);
-                        node_queue.push(Rc::clone(new_node));
+                        let new_node = graph.add_node(Node::from(
+                            x,
+                            y,
+                            xy_to_image_buff_location(x, y, height),
+                        ));
+                        graph.add_edge(current_id, new_node, above_idx);
+                        node_queue.push(new_node);
                         break;
                     } else {
                         above_idx += 1;
@@ -244,15 +298,18 @@ impl OptMaze {
                             match graph.find_node_xy(x, y + 1) {
                                 None => break,
                                 Some(to) => {
-                                    graph.add_edge(&current_node, &to, below_idx);
+                                    graph.add_edge(current_id, to, below_idx + 1);
                                     break;
                                 }
                             }
                         }
-                        let new_node =
-                            &graph.add_node(x, y, xy_to_image_buff_location(x, y, height));
-                        graph.add_edge(&current_node, new_node, below_idx);
-                        node_queue.push(Rc::clone(new_node));
+                        let new_node = graph.add_node(Node::from(
+                            x,
+                            y,
+                            xy_to_image_buff_location(x, y, height),
+                        ));
+                        graph.add_edge(current_id, new_node, below_idx);
+                        node_queue.push(new_node);
                         break;
                     } else {
                         below_idx += 1;
@@ -261,13 +318,11 @@ impl OptMaze {
             }
         }
 
-        // finds the end node
-        for node in &graph.nodes {
-            if node.y == height - 1 {
-                end = Rc::clone(&node);
-                break;
-            }
-        }
+        // the index is a u32 for every pixel, far more than the graph once it is built
+        graph.drop_spatial_index();
+
+        // finds the end node, only cells reachable from the start got one
+        let end = graph.find_node(|node| node.y == height - 1);
 
         OptMaze {
             width,
@@ -278,15 +333,50 @@ impl OptMaze {
         }
     }
 
+    // paints the corridors behind the given edges, end nodes included, into an rgb image buffer
+    pub fn paint_edges(&self, image_buffer: &mut [u8], edges: &[EdgeId], color: [u8; 3]) {
+        for &id in edges {
+            let edge = &self.graph[id];
+            let from = self.graph[edge.from];
+            let to = self.graph[edge.to];
+            for x in from.x.min(to.x)..=from.x.max(to.x) {
+                for y in from.y.min(to.y)..=from.y.max(to.y) {
+                    let vec_coord = xy_to_image_buff_location(x, y, self.height);
+                    image_buffer[vec_coord * 3..vec_coord * 3 + 3].copy_from_slice(&color);
+                }
+            }
+        }
+    }
+
+    // paints the given nodes into an rgb image buffer
+    pub fn paint_nodes(&self, image_buffer: &mut [u8], nodes: &[NodeId], color: [u8; 3]) {
+        for &id in nodes {
+            let vec_coord = self.graph[id].vec_coord;
+            image_buffer[vec_coord * 3..vec_coord * 3 + 3].copy_from_slice(&color);
+        }
+    }
+
+    // writes an rgb image buffer as is, for buffers touched up with paint_edges or paint_nodes
+    pub fn write_buffer(&self, filename: &str, image_buffer: &[u8]) {
+        let file = File::create(Path::new(filename)).unwrap();
+        let w = &mut BufWriter::new(file);
+
+        let mut encoder = png::Encoder::new(w, self.width, self.height);
+        encoder.set_color(png::ColorType::RGB);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(image_buffer).unwrap();
+    }
+
     pub fn write_image(
         &mut self,
-        filename: &String,
-        image_buffer: &mut Vec<u8>,
-        is_buffer_greyscale: bool,
+        filename: &str,
+        image_buffer: &mut [u8],
+        _is_buffer_greyscale: bool,
     ) {
-        let path = Path::new(filename.as_str());
+        let path = Path::new(filename);
         let file = File::create(path).unwrap();
-        let ref mut w = BufWriter::new(file);
+        let w = &mut BufWriter::new(file);
 
         let mut encoder = png::Encoder::new(w, self.width, self.height);
         encoder.set_color(png::ColorType::RGB);
@@ -302,103 +392,84 @@ impl OptMaze {
         // }
 
         // apply graph nodes to image
-        for node in self.graph.nodes.iter() {
-            image_buffer[(node.vec_coord * 3) as usize] = 0;
-            image_buffer[(node.vec_coord * 3 + 1) as usize] = 0;
-            image_buffer[(node.vec_coord * 3 + 2) as usize] = 255;
+        for (_, node) in self.graph.nodes() {
+            image_buffer[node.vec_coord * 3] = 0;
+            image_buffer[node.vec_coord * 3 + 1] = 0;
+            image_buffer[node.vec_coord * 3 + 2] = 255;
         }
 
-        for (from, edge_vec) in self.graph.edges.iter() {
-            for edge in edge_vec {
-                let path_to_create = (
-                    (edge.to.x as i32 - edge.from.x as i32),
-                    (edge.to.y as i32 - edge.from.y as i32),
-                );
-                // println!("{} {}", path_to_create.0, path_to_create.1);
-                if path_to_create.0 == 0 {
-                    if path_to_create.1 < 0 {
-                        // if path goes up DARK RED
-                        println!("Up");
-                        println!("Weight: {}", edge.weight);
-                        println!("From  : {} {}", edge.from.x, edge.from.y);
-                        println!("To    : {} {}", edge.to.x, edge.to.y);
-                        print!("Path: ");
-                        for i in 1..(-path_to_create.1) as u32 {
-                            print!("({} {})", edge.from.x, edge.from.y - i);
-                            let vec_coord = xy_to_image_buff_location(
-                                edge.from.x,
-                                edge.from.y - i,
-                                self.height,
-                            );
-                            image_buffer[(vec_coord * 3) as usize] = 128;
-                            image_buffer[(vec_coord * 3 + 1) as usize] = 0;
-                            image_buffer[(vec_coord * 3 + 2) as usize] = 0;
-                        }
-                        println!();
-                        println!();
-                    } else {
-                        // if path goes down RED
-                        println!("Down");
-                        println!("Weight: {}", edge.weight);
-                        println!("From  : {} {}", edge.from.x, edge.from.y);
-                        println!("To    : {} {}", edge.to.x, edge.to.y);
-                        print!("Path: ");
-                        for i in 1..(path_to_create.1) as u32 {
-                            print!("({} {})", edge.from.x, edge.from.y + i);
-                            let vec_coord = xy_to_image_buff_location(
-                                edge.from.x,
-                                edge.from.y + i,
-                                self.height,
-                            );
-                            image_buffer[(vec_coord * 3) as usize] = 255;
-                            image_buffer[(vec_coord * 3 + 1) as usize] = 0;
-                            image_buffer[(vec_coord * 3 + 2) as usize] = 0;
-                        }
-                        println!();
-                        println!();
+        for (_, edge) in self.graph.edges() {
+            let from = self.graph[edge.from];
+            let to = self.graph[edge.to];
+            let path_to_create = ((to.x as i32 - from.x as i32), (to.y as i32 - from.y as i32));
+            // println!("{} {}", path_to_create.0, path_to_create.1);
+            if path_to_create.0 == 0 {
+                if path_to_create.1 < 0 {
+                    // if path goes up DARK RED
+                    println!("Up");
+                    println!("Weight: {}", edge.weight);
+                    println!("From  : {} {}", from.x, from.y);
+                    println!("To    : {} {}", to.x, to.y);
+                    print!("Path: ");
+                    for i in 1..(-path_to_create.1) as u32 {
+                        print!("({} {})", from.x, from.y - i);
+                        let vec_coord = xy_to_image_buff_location(from.x, from.y - i, self.height);
+                        image_buffer[vec_coord * 3] = 128;
+                        image_buffer[vec_coord * 3 + 1] = 0;
+                        image_buffer[vec_coord * 3 + 2] = 0;
                     }
+                    println!();
+                    println!();
                 } else {
-                    if path_to_create.0 < 0 {
-                        // if path goes left MAGENTA
-                        println!("Left");
-                        println!("Weight: {}", edge.weight);
-                        println!("From  : {} {}", edge.from.x, edge.from.y);
-                        println!("To    : {} {}", edge.to.x, edge.to.y);
-                        print!("Path: ");
-                        for i in 1..(-path_to_create.0) as u32 {
-                            print!("({} {})", edge.from.x - i, edge.from.y);
-                            let vec_coord = xy_to_image_buff_location(
-                                edge.from.x - i,
-                                edge.from.y,
-                                self.height,
-                            );
-                            image_buffer[(vec_coord * 3) as usize] = 255;
-                            image_buffer[(vec_coord * 3 + 1) as usize] = 0;
-                            image_buffer[(vec_coord * 3 + 2) as usize] = 255;
-                        }
-                        println!();
-                        println!();
-                    } else {
-                        // if path goes right YELLOW
-                        println!("Right");
-                        println!("Weight: {}", edge.weight);
-                        println!("From  : {} {}", edge.from.x, edge.from.y);
-                        println!("To    : {} {}", edge.to.x, edge.to.y);
-                        print!("Path: ");
-                        for i in 1..(path_to_create.0) as u32 {
-                            print!("({} {})", edge.from.x + i, edge.from.y);
-                            let vec_coord = xy_to_image_buff_location(
-                                edge.from.x + i,
-                                edge.from.y,
-                                self.height,
-                            );
-                            image_buffer[(vec_coord * 3) as usize] = 255;
-                            image_buffer[(vec_coord * 3 + 1) as usize] = 255;
-                            image_buffer[(vec_coord * 3 + 2) as usize] = 0;
-                        }
-                        println!();
-                        println!();
+                    // if path goes down RED
+                    println!("Down");
+                    println!("Weight: {}", edge.weight);
+                    println!("From  : {} {}", from.x, from.y);
+                    println!("To    : {} {}", to.x, to.y);
+                    print!("Path: ");
+                    for i in 1..(path_to_create.1) as u32 {
+                        print!("({} {})", from.x, from.y + i);
+                        let vec_coord = xy_to_image_buff_location(from.x, from.y + i, self.height);
+                        image_buffer[vec_coord * 3] = 255;
+                        image_buffer[vec_coord * 3 + 1] = 0;
+                        image_buffer[vec_coord * 3 + 2] = 0;
+                    }
+                    println!();
+                    println!();
+                }
+            } else {
+                if path_to_create.0 < 0 {
+                    // if path goes left MAGENTA
+                    println!("Left");
+                    println!("Weight: {}", edge.weight);
+                    println!("From  : {} {}", from.x, from.y);
+                    println!("To    : {} {}", to.x, to.y);
+                    print!("Path: ");
+                    for i in 1..(-path_to_create.0) as u32 {
+                        print!("({} {})", from.x - i, from.y);
+                        let vec_coord = xy_to_image_buff_location(from.x - i, from.y, self.height);
+                        image_buffer[vec_coord * 3] = 255;
+                        image_buffer[vec_coord * 3 + 1] = 0;
+                        image_buffer[vec_coord * 3 + 2] = 255;
+                    }
+                    println!();
+                    println!();
+                } else {
+                    // if path goes right YELLOW
+                    println!("Right");
+                    println!("Weight: {}", edge.weight);
+                    println!("From  : {} {}", from.x, from.y);
+                    println!("To    : {} {}", to.x, to.y);
+                    print!("Path: ");
+                    for i in 1..(path_to_create.0) as u32 {
+                        print!("({} {})", from.x + i, from.y);
+                        let vec_coord = xy_to_image_buff_location(from.x + i, from.y, self.height);
+                        image_buffer[vec_coord * 3] = 255;
+                        image_buffer[vec_coord * 3 + 1] = 255;
+                        image_buffer[vec_coord * 3 + 2] = 0;
                     }
+                    println!();
+                    println!();
                 }
             }
         }
@@ -410,6 +481,203 @@ impl OptMaze {
         );
     }
 
+    // DOT export that keeps the pixel layout, every pixel is `scale` points apart
+    // `neato -n` draws the nodes where they sit in the image, start is green and the exit red
+    pub fn write_dot<W: Write>(&self, out: &mut W, scale: f64) -> io::Result<()> {
+        let height = self.height;
+        self.graph.write_dot_with(
+            out,
+            &[
+                ("shape", String::from("point")),
+                ("width", format!("{}", scale / 72.0)),
+            ],
+            |id, node| {
+                let x = node.x as f64 * scale;
+                let y = (height - 1 - node.y) as f64 * scale;
+                let color = if id == self.start {
+                    "green"
+                } else if Some(id) == self.end {
+                    "red"
+                } else {
+                    "blue"
+                };
+                vec![
+                    ("pos", format!("{},{}", x, y)),
+                    ("color", String::from(color)),
+                ]
+            },
+            |_, edge| vec![("label", edge.weight.to_string())],
+        )
+    }
+
+    pub fn to_dot(&self, scale: f64) -> String {
+        let mut out = Vec::new();
+        self.write_dot(&mut out, scale)
+            .expect("writing to a Vec can't fail");
+        String::from_utf8(out).expect("DOT output is always utf8")
+    }
+
+    pub fn graph(&self) -> &Graph<Node, u32> {
+        &self.graph
+    }
+
+    pub fn start(&self) -> NodeId {
+        self.start
+    }
+
+    // None when the exit can't be reached from the start
+    pub fn end(&self) -> Option<NodeId> {
+        self.end
+    }
+
+    // cheap check that the exit can be reached at all before solving
+    // the solve methods return None, and chokepoints and disjoint_routes nothing, when it can't
+    pub fn is_solvable(&self) -> bool {
+        match self.end {
+            Some(end) => self
+                .graph
+                .connected_components()
+                .same_component(self.start, end),
+            None => false,
+        }
+    }
+
+    // a loop in the corridors, None when the maze is perfect
+    pub fn find_loop(&self) -> Option<Cycle> {
+        self.graph.find_cycle()
+    }
+
+    // corridors and junctions every route from the start to the exit has to go through
+    pub fn chokepoints(&self) -> Chokepoints {
+        let path = match self.solve() {
+            Some(path) => path,
+            None => {
+                return Chokepoints {
+                    corridors: Vec::new(),
+                    junctions: Vec::new(),
+                }
+            }
+        };
+        let mut on_path = vec![false; self.graph.edge_count()];
+        for pair in path.nodes.windows(2) {
+            if let Some(edge) = self.graph.find_edge(pair[0], pair[1]) {
+                on_path[edge.index()] = true;
+            }
+        }
+        let corridors = self
+            .graph
+            .bridges()
+            .into_iter()
+            .filter(|bridge| on_path[bridge.index()])
+            .collect();
+        Chokepoints {
+            corridors,
+            junctions: self.junctions_on(&path.nodes),
+        }
+    }
+
+    // the articulation points inside the path that no detour gets around
+    // a junction with a dead end off it is an articulation point, but a loop can still bypass it
+    fn junctions_on(&self, path: &[NodeId]) -> Vec<NodeId> {
+        let n = self.graph.node_count();
+        let mut position = vec![usize::MAX; n];
+        for (i, node) in path.iter().enumerate() {
+            position[node.index()] = i;
+        }
+        // the parts of the maze off the path, and the lowest and highest path position each one touches
+        let mut regions = UnionFind::new(n);
+        for (_, edge) in self.graph.edges() {
+            let (from, to) = (edge.from.index(), edge.to.index());
+            if position[from] == usize::MAX && position[to] == usize::MAX {
+                regions.union(from, to);
+            }
+        }
+        let mut touches = vec![(usize::MAX, 0); n];
+        // +1 where a detour starts skipping path nodes, -1 where it rejoins
+        let mut skipped = vec![0i64; path.len() + 1];
+        let mut detour = |low: usize, high: usize| {
+            if high > low + 1 {
+                skipped[low + 1] += 1;
+                skipped[high] -= 1;
+            }
+        };
+        for (_, edge) in self.graph.edges() {
+            let (from, to) = (edge.from.index(), edge.to.index());
+            let (on, off) = match (position[from], position[to]) {
+                (usize::MAX, usize::MAX) => continue,
+                (i, usize::MAX) => (i, to),
+                (usize::MAX, i) => (i, from),
+                (i, j) => {
+                    detour(i.min(j), i.max(j));
+                    continue;
+                }
+            };
+            let region = &mut touches[regions.find(off)];
+            *region = (region.0.min(on), region.1.max(on));
+        }
+        for &(low, high) in &touches {
+            if low != usize::MAX {
+                detour(low, high);
+            }
+        }
+
+        let mut bypassed = vec![false; path.len()];
+        let mut open = 0;
+        for (i, skip) in bypassed.iter_mut().enumerate() {
+            open += skipped[i];
+            *skip = open > 0;
+        }
+        self.graph
+            .articulation_points()
+            .into_iter()
+            .filter(|node| {
+                // the start and the exit are on every route anyway
+                let i = position[node.index()];
+                (1..path.len() - 1).contains(&i) && !bypassed[i]
+            })
+            .collect()
+    }
+
+    // a walk down every corridor exactly once, None when the maze has no such walk
+    pub fn inspection_route(&self) -> Option<Trail> {
+        self.graph.eulerian_path()
+    }
+
+    // how many routes from the start to the exit exist that never share a corridor
+    pub fn disjoint_routes(&self) -> usize {
+        match self.end {
+            Some(end) => self.graph.edge_disjoint_paths(self.start, end),
+            None => 0,
+        }
+    }
+
+    // shortest route from the start to the exit, using the corridor lengths as weights
+    pub fn solve(&self) -> Option<crate::graph::Path<u32>> {
+        self.graph.dijkstra(self.start, self.end?)
+    }
+
+    // same as solve but goal directed, Manhattan is the heuristic that fits the corridors
+    // on the sample mazes the route winds far from the straight line, so it settles nearly as many nodes
+    // as solve and takes about as long, it pays off when the route runs close to the straight line
+    pub fn solve_astar(&self, heuristic: Heuristic) -> Option<crate::graph::Path<u32>> {
+        self.graph.astar_with(self.start, self.end?, heuristic)
+    }
+
+    // the k shortest routes that never pass the same junction twice, shortest first
+    // braided mazes have plenty, a perfect maze only ever has one
+    // takes about as long as solve on the sample mazes, 5k.png gives 50 routes in the time solve takes for one
+    pub fn solve_k_shortest(&self, k: usize) -> Vec<crate::graph::Path<u32>> {
+        match self.end {
+            Some(end) => self.graph.k_shortest_paths(self.start, end, k),
+            None => Vec::new(),
+        }
+    }
+
+    // same as solve but searched from the start and the exit at once
+    pub fn solve_bidirectional(&self) -> Option<crate::graph::Path<u32>> {
+        self.graph.bidirectional_dijkstra(self.start, self.end?)
+    }
+
     pub fn print(&self) {
         self.graph.print();
     }